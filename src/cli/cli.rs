@@ -1,44 +1,158 @@
-use classfile_parser::ClassFile;
+use std::error::Error;
+use std::{fs, path::{Path, PathBuf}};
 
-use crate::rules::java::parse::parse_file;
-use crate::config::config::*;
-use crate::types::rule::*;
+use testability_linter::config::config::*;
+use testability_linter::config::init::*;
+use testability_linter::errors::generic::*;
+use testability_linter::report::{console::ConsoleReporter, json::JsonReporter, sarif::SarifReporter};
+use testability_linter::report::reporter::{report, Reporters};
+use testability_linter::{Input, Linter, Registry, Reporter};
 
-pub fn lint_files(files: Vec<String>, rule: &Rule) -> i32 {
-    let class_files: Vec<(ClassFile, &String)> = files
-        .iter()
-        .filter_map(|file| match parse_file(&file) {
-            Ok(class_file) => Some((class_file, file)),
-            Err(e) => {
-                eprintln!("{}", e);
-                None
-            }
-        })
-        .collect();
+const DEFAULT_CONFIG_FILE: &str = "rules.toml";
+/// Exit code when a rule failed with the `error` severity, the other errors
+/// exiting with the code of their [`ErrorCategory`].
+const EXIT_FAILURES: i32 = 1;
 
-    if class_files.is_empty() {
-        return 1;
+/// Lint the given class files, directories and archives.
+///
+/// * `args`: the arguments following the configuration file,
+///   `[--report <format>[:<path>]]... <file>...`
+pub fn lint_files(args: Vec<String>, config: Config, registry: &Registry) -> i32 {
+    let Some((mut reporters, files)) = parse_reports(&args) else {
+        return ErrorCategory::Config.exit_code();
+    };
+    if files.is_empty() {
+        eprintln!("You didn't give any file to lint");
+        return ErrorCategory::Config.exit_code();
     }
 
-    let mut code = 0;
+    let linter = match Linter::builder().registry(registry.clone()).config(config).build() {
+        Ok(linter) => linter,
+        Err(e) => return print_error(&e),
+    };
 
-    class_files.iter().for_each(|class_file| {
-        let result = rule.run(&class_file.0, class_file.1);
-        if result.result().is_err() {
-            code = 1;
-        }
-        println!("{}", result);
+    let inputs: Vec<Input> = files.iter().map(|file| Input::Path(PathBuf::from(file))).collect();
+    let diagnostics = linter.lint(&inputs);
+
+    if let Err(e) = report(&linter, &diagnostics, &mut reporters) {
+        return print_error(&e);
+    }
+
+    diagnostics.unused_suppressions().iter().for_each(|suppression| {
+        eprintln!(
+            "warning: suppression ({}) justified by \"{}\" matched nothing and can be removed",
+            suppression,
+            suppression.justification()
+        );
     });
-    return code;
+
+    match (diagnostics.error_category(), diagnostics.has_errors()) {
+        (Some(category), _) => category.exit_code(),
+        (None, true) => EXIT_FAILURES,
+        (None, false) => 0,
+    }
+}
+
+/// Print an error with the chain of its sources, returning the exit code of its category.
+pub fn print_error(error: &IError) -> i32 {
+    eprintln!("{}", error);
+    let mut source = error.source();
+    while let Some(cause) = source {
+        eprintln!("  caused by: {}", cause);
+        source = cause.source();
+    }
+    error.category().exit_code()
 }
 
-pub fn create_rules_list(config_file: String) -> Option<Vec<Rule>> {
-    let table = match read_config(config_file) {
-        Ok(table) => table,
-        Err(e) => { eprintln!("{}", e); return None; }
+/// Split the `--report` options from the files to lint, reporting to the
+/// console when no report is asked for.
+fn parse_reports(args: &[String]) -> Option<(Reporters, Vec<String>)> {
+    let mut reporters = Vec::new();
+    let mut files = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--report" => match args.next().map(|spec| (spec, create_reporter(spec))) {
+                Some((_, Some(reporter))) => reporters.push(reporter),
+                Some((spec, None)) => { eprintln!("Unknown report format {}, expected console, json[:<path>] or sarif[:<path>]", spec); return None; }
+                None => { eprintln!("--report expects a format"); return None; }
+            },
+            _ => files.push(arg.to_owned()),
+        }
+    }
+
+    if reporters.is_empty() {
+        reporters.push(Box::new(ConsoleReporter));
+    }
+    Some((reporters, files))
+}
+
+/// Create the reporter of a `<format>[:<path>]` specification.
+fn create_reporter(spec: &str) -> Option<Box<dyn Reporter>> {
+    let (format, output) = match spec.split_once(':') {
+        Some((format, path)) => (format, Some(PathBuf::from(path))),
+        None => (spec, None),
     };
 
-    select_rules(table)
+    match (format, output) {
+        ("console", None) => Some(Box::new(ConsoleReporter)),
+        ("json", output) => Some(Box::new(JsonReporter::new(output))),
+        ("sarif", output) => Some(Box::new(SarifReporter::new(output))),
+        _ => None,
+    }
+}
+
+/// Read the configuration file, failing when it cannot be read or selects no rule.
+pub fn create_config(config_file: String, registry: &Registry) -> Result<Config, IError> {
+    let table = read_config(config_file.to_owned())?;
+    Config::new(table, registry).map_err(|e| e.with_path(&config_file))
+}
+
+/// Write a starter configuration, by default to `rules.toml`.
+///
+/// * `args`: the arguments following `init`, `[<output>] [--from-scan <dir>]`
+pub fn init_config(args: &[String], registry: &Registry) -> i32 {
+    let mut output = String::from(DEFAULT_CONFIG_FILE);
+    let mut scan_directory_path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from-scan" => match args.next() {
+                Some(directory) => scan_directory_path = Some(directory),
+                None => { eprintln!("--from-scan expects a directory"); return ErrorCategory::Config.exit_code(); }
+            },
+            _ => output = arg.to_owned(),
+        }
+    }
+
+    if Path::new(&output).exists() {
+        eprintln!("{} already exists, refusing to overwrite it", output);
+        return ErrorCategory::Config.exit_code();
+    }
+
+    let scan = match scan_directory_path.map(|directory| scan_directory(directory, registry)) {
+        Some(Ok(scan)) => Some(scan),
+        Some(Err(e)) => return print_error(&e),
+        None => None,
+    };
+
+    // Like unanalysed inputs when linting, unparsable classes decide the exit code.
+    let code = scan
+        .iter()
+        .flat_map(|scan| scan.errors())
+        .map(|error| {
+            print_error(error);
+            error.category()
+        })
+        .max()
+        .map_or(0, ErrorCategory::exit_code);
+
+    match fs::write(&output, starter_config(registry, scan.as_ref())) {
+        Ok(()) => { println!("Wrote {}", output); code }
+        Err(e) => { eprintln!("Failed to write {}: {}", output, e); ErrorCategory::Input.exit_code() }
+    }
 }
 
 #[cfg(test)]
@@ -53,9 +167,9 @@ mod tests {
     #[case::all_and_other_rules("tests/inputs/config/valid/unnecessary_rules.toml", true)]
     #[case::some_rules("tests/inputs/config/valid/some_rules.toml", true)]
     #[case::some_and_other_rules("tests/inputs/config/valid/some_unnecessary_rules.toml", true)]
-    fn test_create_rules_list(#[case] file: &str, #[case] expected: bool) {
-        let option_vec = create_rules_list(String::from(file));
-        assert_eq!(option_vec.is_some(), expected);
+    fn test_create_config(#[case] file: &str, #[case] expected: bool) {
+        let config = create_config(String::from(file), &Registry::default());
+        assert_eq!(config.is_ok(), expected);
     }
 
     #[rstest]
@@ -65,12 +179,31 @@ mod tests {
     #[case::all_and_other_rules("tests/inputs/config/valid/unnecessary_rules.toml", 3)]
     #[case::some_rules("tests/inputs/config/valid/some_rules.toml", 1)]
     #[case::some_and_other_rules("tests/inputs/config/valid/some_unnecessary_rules.toml", 2)]
-    fn test_create_rules_list_number(#[case] file: &str, #[case] expected: usize) {
-        let option_vec = create_rules_list(String::from(file));
-        match option_vec {
-            None => assert_eq!(0, expected),
-            Some(vec) => assert_eq!(vec.len(), expected)
+    fn test_create_config_rules_number(#[case] file: &str, #[case] expected: usize) {
+        match create_config(String::from(file), &Registry::default()) {
+            Err(_) => assert_eq!(0, expected),
+            Ok(config) => assert_eq!(config.rules().len(), expected)
         }
     }
+
+    #[rstest]
+    #[case::default(&[], 1, 0)]
+    #[case::files_only(&["A.class", "B.class"], 1, 2)]
+    #[case::several(&["--report", "console", "A.class", "--report", "json:out/lint.json", "--report", "sarif"], 3, 1)]
+    fn test_parse_reports(#[case] args: &[&str], #[case] reporters: usize, #[case] files: usize) {
+        let args: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+        let (parsed_reporters, parsed_files) = parse_reports(&args).unwrap();
+        assert_eq!(parsed_reporters.len(), reporters);
+        assert_eq!(parsed_files.len(), files);
+    }
+
+    #[rstest]
+    #[case::missing_format(&["A.class", "--report"])]
+    #[case::unknown_format(&["--report", "html:out/lint.html"])]
+    #[case::console_to_file(&["--report", "console:out/lint.txt"])]
+    fn test_parse_reports_invalid(#[case] args: &[&str]) {
+        let args: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+        assert!(parse_reports(&args).is_none());
+    }
 }
 
@@ -1,37 +1,145 @@
-use toml::Table;
-use std::{ path::PathBuf, fs};
-use crate::errors::generic::GenericErrorKind;
-use crate::types::rule::{ RuleKind, Rule };
+use toml::{Table, Value};
+use std::fs;
+use crate::errors::generic::*;
+use crate::rules::java::class::JavaClass;
+use crate::rules::registry::Registry;
+use crate::types::rule::ConfiguredRule;
 
-pub fn read_config(config_file: String) -> Result<Table, GenericErrorKind> {
-    let path = PathBuf::from(config_file.as_str());
+/// Key listing the extra annotation types that silence rules.
+pub const SUPPRESS_ANNOTATIONS_KEY: &str = "suppress_annotations";
 
-    let Ok(content) = fs::read_to_string(path) else {
-        return Err(GenericErrorKind::InvalidPath);
-    };
+/// Key holding the path of the suppressions file.
+pub const SUPPRESSIONS_FILE_KEY: &str = "suppressions_file";
 
-    match content.parse::<Table>() {
-        Ok(table) => Ok(table),
-        Err(_) => Err(GenericErrorKind::ParseError),
+/// Key listing the jars and directories of library classes.
+pub const CLASSPATH_KEY: &str = "classpath";
+
+/// Key telling whether anonymous classes are checked.
+pub const ANONYMOUS_CLASSES_KEY: &str = "anonymous_classes";
+
+/// Key telling whether classes generated by the compiler are checked.
+pub const SYNTHETIC_CLASSES_KEY: &str = "synthetic_classes";
+
+/// The rules to run and the settings shared by all of them.
+#[derive(Debug)]
+pub struct Config {
+    rules: Vec<ConfiguredRule>,
+    suppress_annotations: Vec<String>,
+    suppressions_file: Option<String>,
+    classpath: Vec<String>,
+    anonymous_classes: bool,
+    synthetic_classes: bool,
+}
+
+impl Config {
+    /// Read the settings and the rules of a configuration, failing on an
+    /// invalid rule entry or when no rule is enabled.
+    pub fn new(table: Table, registry: &Registry) -> Result<Self, IError> {
+        let suppress_annotations = select_strings(&table, SUPPRESS_ANNOTATIONS_KEY);
+        let classpath = select_strings(&table, CLASSPATH_KEY);
+        let suppressions_file = table
+            .get(SUPPRESSIONS_FILE_KEY)
+            .and_then(Value::as_str)
+            .map(String::from);
+        let anonymous_classes = table.get(ANONYMOUS_CLASSES_KEY).and_then(Value::as_bool).unwrap_or(true);
+        let synthetic_classes = table.get(SYNTHETIC_CLASSES_KEY).and_then(Value::as_bool).unwrap_or(true);
+        let rules = select_rules(table, registry)?;
+        if rules.is_empty() {
+            return Err(IError::new(
+                GenericErrorKind::Config,
+                String::from("You didn't select rules in the config file"),
+            ));
+        }
+        Ok(Self {
+            rules,
+            suppress_annotations,
+            suppressions_file,
+            classpath,
+            anonymous_classes,
+            synthetic_classes,
+        })
+    }
+
+    pub fn rules(&self) -> &Vec<ConfiguredRule> {
+        &self.rules
     }
+
+    pub fn suppress_annotations(&self) -> &Vec<String> {
+        &self.suppress_annotations
+    }
+
+    pub fn suppressions_file(&self) -> Option<&String> {
+        self.suppressions_file.as_ref()
+    }
+
+    /// The jars and directories of the library classes the analysed classes
+    /// extend, indexed but not checked.
+    pub fn classpath(&self) -> &Vec<String> {
+        &self.classpath
+    }
+
+    /// Whether a class is checked, or left out with the anonymous or
+    /// synthetic classes.
+    pub fn checks(&self, class: &JavaClass) -> bool {
+        (self.anonymous_classes || !class.nesting().is_anonymous())
+            && (self.synthetic_classes || !class.is_synthetic())
+    }
+}
+
+pub fn read_config(config_file: String) -> Result<Table, IError> {
+    read_toml(&config_file)
 }
 
-pub fn select_rules(table: Table) -> Option<Vec<Rule>> {
-    let mut vector: Vec<Rule> = Vec::new();
+/// Read a TOML file, failing with a configuration error located at the
+/// file and, for syntax errors, the byte they start at.
+///
+/// * `file`: the path of the file
+pub fn read_toml(file: &str) -> Result<Table, IError> {
+    let content = fs::read_to_string(file).map_err(|e| {
+        IError::new(GenericErrorKind::InvalidPath, format!("Cannot read {}", file))
+            .with_category(ErrorCategory::Config)
+            .with_path(file)
+            .with_source(e)
+    })?;
 
-    RuleKind::iterator().for_each(|kind| {
-        let rule = Rule::new(&table, kind.to_key());
-        match rule {
-            Some(rule) => vector.push(rule),
-            None => (),
+    content.parse::<Table>().map_err(|e| {
+        let error = IError::new(GenericErrorKind::ParseError, format!("Invalid TOML in {}", file))
+            .with_category(ErrorCategory::Config)
+            .with_path(file);
+        match e.span() {
+            Some(span) => error.with_offset(span.start),
+            None => error,
         }
-    });
+        .with_source(e)
+    })
+}
+
+pub fn select_rules(table: Table, registry: &Registry) -> Result<Vec<ConfiguredRule>, IError> {
+    let mut vector: Vec<ConfiguredRule> = Vec::new();
 
-    if vector.is_empty() {
-        return None;
+    for rule in registry.rules() {
+        let Some(value) = table.get(rule.id()) else {
+            continue;
+        };
+        // A disabled rule is still checked, so a typo doesn't go unnoticed.
+        let configured = ConfiguredRule::new(rule.clone(), value)?;
+        if ConfiguredRule::is_enabled(value) {
+            vector.push(configured);
+        }
     }
 
-    Some(vector)
+    Ok(vector)
+}
+
+/// Read an array of strings, e.g. the annotation names under `suppress_annotations`.
+pub fn select_strings(table: &Table, key: &str) -> Vec<String> {
+    match table.get(key) {
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(|value| value.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
 }
 
 #[cfg(test)]
@@ -51,18 +159,42 @@ mod tests {
         assert_eq!(result.is_ok(), expected);
     }
 
+    #[test]
+    fn test_read_config_error() {
+        let file = "tests/inputs/config/invalid/malformed_file.toml";
+        let error = read_config(String::from(file)).unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Config);
+        assert_eq!(error.path().map(String::as_str), Some(file));
+        assert!(error.offset().is_some());
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
     #[rstest]
     #[case::all_rules("tests/inputs/config/valid/all_rules.toml", 3)]
     #[case::all_and_other_rules("tests/inputs/config/valid/unnecessary_rules.toml", 3)]
     #[case::some_rules("tests/inputs/config/valid/some_rules.toml", 1)]
     #[case::some_and_other_rules("tests/inputs/config/valid/some_unnecessary_rules.toml", 2)]
+    #[case::table_rules("tests/inputs/config/valid/table_rules.toml", 2)]
     fn test_select_rules(#[case] file: &str, #[case] expected: usize) {
         let table = read_config(String::from(file)).unwrap();
-        let option_vec = select_rules(table);
-        match option_vec {
-            None => assert_eq!(0, expected),
-            Some(vec) => assert_eq!(vec.len(), expected)
-        }
+        let rules = select_rules(table, &Registry::default()).unwrap();
+        assert_eq!(rules.len(), expected);
+    }
+
+    #[rstest]
+    #[case::out_of_range("too_many_arguments = 0")]
+    #[case::disabled_with_typo("[check_no_void]\nenabled = false\nseverity = \"warn\"")]
+    fn test_select_rules_invalid(#[case] toml: &str) {
+        let error = select_rules(toml.parse::<Table>().unwrap(), &Registry::default()).unwrap_err();
+        assert_eq!(error.category().exit_code(), 2);
     }
-}
 
+    #[rstest]
+    #[case::none("check_no_void = true", 0)]
+    #[case::some("suppress_annotations = [\"com.example.Generated\", \"Other\"]", 2)]
+    #[case::not_an_array("suppress_annotations = \"com.example.Generated\"", 0)]
+    fn test_select_strings(#[case] toml: &str, #[case] expected: usize) {
+        let table = toml.parse::<Table>().unwrap();
+        assert_eq!(select_strings(&table, SUPPRESS_ANNOTATIONS_KEY).len(), expected);
+    }
+}
@@ -0,0 +1,303 @@
+use std::path::Path;
+use std::sync::Arc;
+use toml::Value;
+
+use crate::config::config::{
+    ANONYMOUS_CLASSES_KEY, CLASSPATH_KEY, SUPPRESSIONS_FILE_KEY, SUPPRESS_ANNOTATIONS_KEY, SYNTHETIC_CLASSES_KEY,
+};
+use crate::errors::generic::IError;
+use crate::rules::java::class::JavaClass;
+use crate::rules::java::hierarchy::ClassIndex;
+use crate::rules::java::project::Project;
+use crate::rules::registry::Registry;
+use crate::types::rule::{ConfiguredRule, OptionKind, Rule};
+use crate::types::scope::SCOPE_KEY;
+use crate::utils::path::find_class_files;
+
+/// The rule values measured on an existing codebase by `init --from-scan`.
+#[derive(Debug)]
+pub struct Scan {
+    directory: String,
+    files: usize,
+    values: Vec<(&'static str, Value)>,
+    /// The class files that could not be parsed, left out of the scan.
+    errors: Vec<IError>,
+}
+
+impl Scan {
+    pub fn directory(&self) -> &String {
+        &self.directory
+    }
+
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    pub fn errors(&self) -> &Vec<IError> {
+        &self.errors
+    }
+
+    /// The shorthand value of a rule: whether it passes, or its passing threshold.
+    pub fn value(&self, rule: &str) -> Option<&Value> {
+        self.values
+            .iter()
+            .find(|(scanned, _)| *scanned == rule)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Render a commented starter configuration listing every rule of the registry.
+///
+/// * `registry`: the rules to list
+/// * `scan`: values measured on a codebase, used instead of the rule defaults
+pub fn starter_config(registry: &Registry, scan: Option<&Scan>) -> String {
+    let mut config = String::from("# testability-linter configuration\n");
+    match scan {
+        Some(scan) => {
+            config.push_str(
+                format!(
+                    "# Thresholds set so the {} class file(s) under {} pass; tighten them over time.\n",
+                    scan.files(),
+                    scan.directory()
+                )
+                .as_str(),
+            );
+            if !scan.errors().is_empty() {
+                config.push_str(
+                    format!("# {} class file(s) could not be parsed and were left out.\n", scan.errors().len()).as_str(),
+                );
+            }
+        }
+        None => config.push_str("# Every rule is set to its default value.\n"),
+    }
+
+    config.push_str("\n# Annotation types that, besides @SuppressTestability, silence the rules named in their value.\n");
+    config.push_str(format!("# {} = [\"com.example.Generated\"]\n", SUPPRESS_ANNOTATIONS_KEY).as_str());
+
+    config.push_str("\n# A file of justified [[suppress]] entries: rule, class glob, optional method, justification.\n");
+    config.push_str(format!("# {} = \"suppressions.toml\"\n", SUPPRESSIONS_FILE_KEY).as_str());
+
+    config.push_str("\n# Jars and directories of library classes, used to find the methods fixed by a supertype.\n");
+    config.push_str(format!("# {} = [\"lib/framework.jar\"]\n", CLASSPATH_KEY).as_str());
+
+    config.push_str("\n# Leave out anonymous classes, or classes generated by the compiler.\n");
+    config.push_str(format!("# {} = false\n", ANONYMOUS_CLASSES_KEY).as_str());
+    config.push_str(format!("# {} = false\n", SYNTHETIC_CLASSES_KEY).as_str());
+
+    registry.rules().iter().for_each(|rule| {
+        let scanned = scan.and_then(|scan| scan.value(rule.id()));
+        config.push_str(rule_section(rule.as_ref(), scanned).as_str());
+    });
+
+    config
+}
+
+fn rule_section(rule: &dyn Rule, scanned: Option<&Value>) -> String {
+    let mut section = format!("\n# {}: {}\n", rule.name(), rule.description());
+    section.push_str(format!("# {}\n", rule.rationale()).as_str());
+    section.push_str(format!("[{}]\n", rule.id()).as_str());
+
+    let enabled = scanned != Some(&Value::Boolean(false));
+    if !enabled {
+        section.push_str("# Default: true\n");
+    }
+    section.push_str(format!("enabled = {}\n", enabled).as_str());
+    section.push_str(format!("# severity = \"{}\"\n", rule.default_severity()).as_str());
+    section.push_str(format!("# {} = {}\n", SCOPE_KEY, rule.default_scope().to_toml()).as_str());
+
+    rule.options().iter().enumerate().for_each(|(index, option)| {
+        let value = match scanned {
+            Some(value) if index == 0 && option.accepts(value) => value,
+            _ => option.default(),
+        };
+        section.push_str(format!("# {}\n", option.description()).as_str());
+        if value != option.default() {
+            section.push_str(format!("# Default: {}\n", option.default()).as_str());
+        }
+        section.push_str(format!("{} = {}\n", option.name(), value).as_str());
+    });
+
+    section
+}
+
+/// Parse every class file under a directory and find, for each rule, the
+/// strictest value the classes pass with.
+///
+/// * `directory`: the directory holding the compiled classes
+/// * `registry`: the rules to measure
+pub fn scan_directory(directory: &str, registry: &Registry) -> Result<Scan, IError> {
+    let mut errors = Vec::new();
+    let classes: Vec<JavaClass> = find_class_files(Path::new(directory))?
+        .iter()
+        .filter_map(|file| match JavaClass::parse(file) {
+            Ok(class) => Some(class),
+            Err(e) => {
+                errors.push(e);
+                None
+            }
+        })
+        .collect();
+    let index = Arc::new(ClassIndex::new(&classes));
+    let classes: Vec<JavaClass> = classes.into_iter().map(|class| class.with_index(index.clone())).collect();
+
+    let values = registry
+        .rules()
+        .iter()
+        .map(|rule| (rule.id(), strictest_passing_value(registry, rule.id(), &classes, &index)))
+        .collect();
+
+    Ok(Scan {
+        directory: String::from(directory),
+        files: classes.len(),
+        values,
+        errors,
+    })
+}
+
+fn strictest_passing_value(registry: &Registry, id: &str, classes: &[JavaClass], index: &ClassIndex) -> Value {
+    let Some(rule) = registry.get(id) else {
+        return Value::Boolean(false);
+    };
+    let passes = |value: &Value| match ConfiguredRule::new(rule.clone(), value) {
+        Ok(configured) => configured
+            .run_all(&Project::new(classes, index))
+            .iter()
+            .all(|result| result.result().is_ok()),
+        // A value the rule cannot be configured with is no threshold to settle on.
+        Err(_) => false,
+    };
+
+    match rule.options().first().map(|option| option.kind()) {
+        Some(OptionKind::Integer { min, max }) => {
+            // No threshold lets the classes pass: the rule is written disabled.
+            if !passes(&Value::Integer(max)) {
+                return Value::Boolean(false);
+            }
+            // Thresholds are monotonic: search the lowest one that still passes.
+            let (mut low, mut high) = (min, max);
+            while low < high {
+                let middle = low + (high - low) / 2;
+                if passes(&Value::Integer(middle)) {
+                    high = middle;
+                } else {
+                    low = middle + 1;
+                }
+            }
+            Value::Integer(low)
+        }
+        _ => Value::Boolean(passes(&Value::Boolean(true))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::config::select_rules;
+    use crate::errors::fail::Fail;
+    use crate::errors::generic::GenericErrorKind;
+    use crate::types::rule::{RuleOption, RuleOptions};
+    use rstest::rstest;
+    use toml::Table;
+
+    /// A rule failing on the classes with more methods than its maximum, up to a bound.
+    struct MethodCount(i64);
+
+    impl Rule for MethodCount {
+        fn id(&self) -> &'static str {
+            "method_count"
+        }
+
+        fn name(&self) -> &'static str {
+            "Method Count"
+        }
+
+        fn description(&self) -> &'static str {
+            "A rule with a narrow threshold."
+        }
+
+        fn rationale(&self) -> &'static str {
+            "Tests."
+        }
+
+        fn options(&self) -> Vec<RuleOption> {
+            vec![RuleOption::integer("max", 1, self.0, 1, "Maximum number of methods of a class.")]
+        }
+
+        fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+            if class.class_file().methods.len() as i64 <= options.integer("max") {
+                return Vec::new();
+            }
+            vec![Fail::new(String::new(), String::from("Too many methods"), GenericErrorKind::RuleCheckFailed)]
+        }
+    }
+
+    #[test]
+    fn test_starter_config_enables_every_rule() {
+        let registry = Registry::default();
+        let table = starter_config(&registry, None).parse::<Table>().unwrap();
+        let rules = select_rules(table, &registry).unwrap();
+        assert_eq!(rules.len(), registry.rules().len());
+    }
+
+    #[test]
+    fn test_starter_config_documents_every_rule() {
+        let registry = Registry::default();
+        let config = starter_config(&registry, None);
+        assert!(registry.rules().iter().all(|rule| config.contains(rule.rationale())));
+    }
+
+    #[test]
+    fn test_scan_directory() {
+        let scan = scan_directory("tests/inputs/java/too_many_arguments", &Registry::default()).unwrap();
+        assert_eq!(scan.files(), 2);
+        assert_eq!(scan.value("too_many_arguments"), Some(&Value::Integer(5)));
+        assert_eq!(scan.value("check_no_void"), Some(&Value::Boolean(false)));
+        assert_eq!(scan.value("no_binary_in_names"), Some(&Value::Boolean(true)));
+        assert_eq!(scan.value("cyclomatic_complexity"), Some(&Value::Integer(1)));
+        assert_eq!(scan.value("package_cycles"), Some(&Value::Boolean(true)));
+        assert_eq!(scan.value("static_calls"), Some(&Value::Boolean(true)));
+        assert_eq!(scan.value("new_collaborators"), Some(&Value::Boolean(true)));
+        assert_eq!(scan.value("singletons"), Some(&Value::Boolean(true)));
+        assert_eq!(scan.value("global_state"), Some(&Value::Boolean(true)));
+        assert_eq!(scan.value("constructor_work"), Some(&Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_starter_config_from_scan() {
+        let registry = Registry::default();
+        let scan = scan_directory("tests/inputs/java/too_many_arguments", &registry).unwrap();
+        let table = starter_config(&registry, Some(&scan)).parse::<Table>().unwrap();
+        assert_eq!(table["too_many_arguments"].get("max"), Some(&Value::Integer(5)));
+        assert_eq!(select_rules(table, &registry).unwrap().len(), 9);
+    }
+
+    // The classes have 2 and 6 methods.
+    #[rstest]
+    #[case::within_range(10, Value::Integer(6))]
+    #[case::failing_at_max(3, Value::Boolean(false))]
+    fn test_scan_narrow_range(#[case] max: i64, #[case] expected: Value) {
+        let mut registry = Registry::empty();
+        registry.register(Arc::new(MethodCount(max)));
+        let scan = scan_directory("tests/inputs/java/too_many_arguments", &registry).unwrap();
+        assert_eq!(scan.value("method_count"), Some(&expected));
+
+        let table = starter_config(&registry, Some(&scan)).parse::<Table>().unwrap();
+        let enabled = expected != Value::Boolean(false);
+        assert_eq!(table["method_count"].get("enabled"), Some(&Value::Boolean(enabled)));
+    }
+
+    #[test]
+    fn test_scan_unparsable_class() {
+        let registry = Registry::default();
+        let scan = scan_directory("tests/inputs/java/scan_unparsable", &registry).unwrap();
+        assert_eq!(scan.files(), 0);
+        assert_eq!(scan.errors().len(), 1);
+        assert!(scan.errors()[0].path().is_some_and(|path| path.ends_with("Truncated.class")));
+        assert!(starter_config(&registry, Some(&scan)).contains("1 class file(s) could not be parsed"));
+    }
+
+    #[test]
+    fn test_scan_missing_directory() {
+        assert!(scan_directory("tests/inputs/java/does_not_exist", &Registry::default()).is_err());
+    }
+}
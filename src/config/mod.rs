@@ -1,2 +1,5 @@
+#[allow(clippy::module_inception)]
 pub mod config;
-
+pub mod init;
+pub mod schema;
+pub mod suppressions;
@@ -0,0 +1,180 @@
+use serde_json::{json, Map, Value};
+
+use crate::config::config::{
+    ANONYMOUS_CLASSES_KEY, CLASSPATH_KEY, SUPPRESSIONS_FILE_KEY, SUPPRESS_ANNOTATIONS_KEY, SYNTHETIC_CLASSES_KEY,
+};
+use crate::rules::registry::Registry;
+use crate::types::rule::{OptionKind, Rule, RuleOption, Severity};
+use crate::types::scope::SCOPE_KEY;
+
+const SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Build the JSON Schema of the configuration file from the registered rules.
+///
+/// Unknown keys are left allowed, as the configuration reader ignores them.
+pub fn config_schema(registry: &Registry) -> Value {
+    let mut properties: Map<String, Value> = registry
+        .rules()
+        .iter()
+        .map(|rule| (String::from(rule.id()), rule_schema(rule.as_ref())))
+        .collect();
+
+    properties.insert(
+        String::from(SUPPRESS_ANNOTATIONS_KEY),
+        json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Fully qualified annotation types that, besides @SuppressTestability, silence the rules named in their value.",
+            "default": [],
+        }),
+    );
+    properties.insert(
+        String::from(CLASSPATH_KEY),
+        json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Jars and directories of library classes, indexed to resolve supertypes but not checked.",
+            "default": [],
+        }),
+    );
+    properties.insert(
+        String::from(ANONYMOUS_CLASSES_KEY),
+        json!({
+            "type": "boolean",
+            "description": "Whether anonymous classes are checked.",
+            "default": true,
+        }),
+    );
+    properties.insert(
+        String::from(SYNTHETIC_CLASSES_KEY),
+        json!({
+            "type": "boolean",
+            "description": "Whether classes generated by the compiler, e.g. the lookup tables of switches on enums, are checked.",
+            "default": true,
+        }),
+    );
+    properties.insert(
+        String::from(SUPPRESSIONS_FILE_KEY),
+        json!({
+            "type": "string",
+            "description": "Path of a TOML file of [[suppress]] entries, each with a rule, a class glob, an optional method pattern and a justification.",
+        }),
+    );
+
+    json!({
+        "$schema": SCHEMA_DRAFT,
+        "title": "testability-linter configuration",
+        "type": "object",
+        "properties": properties,
+    })
+}
+
+/// A rule is set with a boolean, with the value of its first option when it is an integer, or with a table.
+fn rule_schema(rule: &dyn Rule) -> Value {
+    let options = rule.options();
+
+    let mut properties = Map::new();
+    properties.insert(
+        String::from("enabled"),
+        json!({ "type": "boolean", "default": true, "description": "Whether the rule runs." }),
+    );
+    properties.insert(
+        String::from("severity"),
+        json!({
+            "enum": [Severity::Error.to_key(), Severity::Warning.to_key()],
+            "default": rule.default_severity().to_key(),
+            "description": "Whether a fail of the rule fails the run or is only reported.",
+        }),
+    );
+    properties.insert(String::from(SCOPE_KEY), rule.default_scope().schema());
+    options.iter().for_each(|option| {
+        properties.insert(String::from(option.name()), option_schema(option));
+    });
+
+    let mut variants = vec![json!({ "type": "boolean" })];
+    let shorthand = options
+        .first()
+        .filter(|option| matches!(option.kind(), OptionKind::Integer { .. }));
+    if let Some(option) = shorthand {
+        variants.push(option_schema(option));
+    }
+    variants.push(json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    }));
+
+    json!({
+        "title": rule.name(),
+        "description": rule.description(),
+        "default": shorthand.map_or(json!(true), |option| to_json(option.default())),
+        "anyOf": variants,
+    })
+}
+
+fn option_schema(option: &RuleOption) -> Value {
+    let mut schema = match option.kind() {
+        OptionKind::Flag => json!({ "type": "boolean" }),
+        OptionKind::Integer { min, max } => json!({
+            "type": "integer",
+            "minimum": min,
+            "maximum": max,
+        }),
+        OptionKind::Strings => json!({ "type": "array", "items": { "type": "string" } }),
+    };
+
+    schema["description"] = json!(option.description());
+    schema["default"] = to_json(option.default());
+    schema
+}
+
+fn to_json(value: &toml::Value) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn rule_schema_for_test(key: &str) -> Value {
+        config_schema(&Registry::default())["properties"][key].clone()
+    }
+
+    #[test]
+    fn test_schema_lists_every_rule() {
+        let registry = Registry::default();
+        let schema = config_schema(&registry);
+        let properties = schema["properties"].as_object().unwrap();
+        assert_eq!(properties.len(), registry.rules().len() + 5);
+        assert!(registry.rules().iter().all(|rule| properties.contains_key(rule.id())));
+    }
+
+    #[rstest]
+    #[case::check_no_void("check_no_void", 2, json!(true))]
+    #[case::no_binary_in_names("no_binary_in_names", 2, json!(true))]
+    #[case::too_many_arguments("too_many_arguments", 3, json!(4))]
+    fn test_rule_schema(#[case] key: &str, #[case] variants: usize, #[case] default: Value) {
+        let rule = rule_schema_for_test(key);
+        assert_eq!(rule["anyOf"].as_array().unwrap().len(), variants);
+        assert_eq!(rule["anyOf"][0]["type"], json!("boolean"));
+        assert_eq!(rule["default"], default);
+        assert!(rule["description"].is_string());
+    }
+
+    #[test]
+    fn test_integer_range() {
+        let rule = rule_schema_for_test("too_many_arguments");
+        assert_eq!(rule["anyOf"][1]["minimum"], json!(1));
+        assert_eq!(rule["anyOf"][1]["maximum"], json!(255));
+        assert_eq!(rule["anyOf"][2]["properties"]["max"], rule["anyOf"][1]);
+    }
+
+    #[test]
+    fn test_table_form() {
+        let rule = rule_schema_for_test("check_no_void");
+        let properties = &rule["anyOf"][1]["properties"];
+        assert_eq!(properties["severity"]["default"], json!("error"));
+        assert_eq!(properties["enabled"]["type"], json!("boolean"));
+    }
+}
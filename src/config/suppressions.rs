@@ -0,0 +1,214 @@
+use regex::Regex;
+use std::fmt;
+use toml::{Table, Value};
+
+use crate::config::config::read_toml;
+use crate::errors::{fail::Fail, generic::*};
+use crate::rules::registry::Registry;
+use crate::utils::pattern::glob_to_regex;
+
+/// A justified exception to a rule, read from the suppressions file.
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    rule: String,
+    class: String,
+    method: Option<String>,
+    justification: String,
+    class_regex: Regex,
+    method_regex: Option<Regex>,
+}
+
+impl Suppression {
+    fn new(index: usize, table: &Table, registry: &Registry) -> Result<Self, IError> {
+        let field = |key: &str| match table.get(key) {
+            Some(Value::String(value)) if !value.trim().is_empty() => Ok(Some(value.to_owned())),
+            None => Ok(None),
+            Some(_) => Err(IError::new(
+                GenericErrorKind::InvalidFormat,
+                format!("Suppression #{}: `{}` must be a non-empty string.", index + 1, key),
+            )),
+        };
+        let required = |key: &str| match field(key)? {
+            Some(value) => Ok(value),
+            None => Err(IError::new(
+                GenericErrorKind::NotFound,
+                format!("Suppression #{}: `{}` is mandatory.", index + 1, key),
+            )),
+        };
+
+        let rule = required("rule")?;
+        if !registry.contains(&rule) {
+            return Err(IError::new(
+                GenericErrorKind::InvalidFormat,
+                format!("Suppression #{}: unknown rule `{}`.", index + 1, rule),
+            ));
+        }
+        let class = required("class")?;
+        let method = field("method")?;
+        let justification = required("justification")?;
+
+        Ok(Self {
+            class_regex: glob_to_regex(&class),
+            method_regex: method.as_deref().map(glob_to_regex),
+            rule,
+            class,
+            method,
+            justification,
+        })
+    }
+
+    pub fn justification(&self) -> &String {
+        &self.justification
+    }
+
+    /// Whether this entry covers a fail.
+    ///
+    /// A method pattern containing `(` is matched against the name followed by
+    /// the descriptor, e.g. `setName(Ljava/lang/String;)V`, otherwise against the name.
+    fn covers(&self, rule: &str, class_name: &str, fail: &Fail) -> bool {
+        if self.rule != rule || !self.class_regex.is_match(class_name) {
+            return false;
+        }
+
+        match (&self.method, &self.method_regex) {
+            (Some(method), Some(regex)) if method.contains('(') => match fail.descriptor() {
+                Some(descriptor) => regex.is_match(format!("{}{}", fail.method(), descriptor).as_str()),
+                None => false,
+            },
+            (_, Some(regex)) => regex.is_match(fail.method()),
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Suppression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rule: {}, class: {}", self.rule, self.class)?;
+        if let Some(method) = &self.method {
+            write!(f, ", method: {}", method)?;
+        }
+        Ok(())
+    }
+}
+
+/// The entries of a suppressions file, a TOML file of `[[suppress]]` tables.
+#[derive(Debug, Clone, Default)]
+pub struct SuppressionFile {
+    entries: Vec<Suppression>,
+}
+
+impl SuppressionFile {
+    /// Read a suppressions file, checking its rules against the registry.
+    pub fn read(file: &str, registry: &Registry) -> Result<Self, IError> {
+        let table = read_toml(file)?;
+        Self::new(&table, registry).map_err(|e| e.with_category(ErrorCategory::Config).with_path(file))
+    }
+
+    pub fn new(table: &Table, registry: &Registry) -> Result<Self, IError> {
+        let entries = match table.get("suppress") {
+            Some(Value::Array(entries)) => entries,
+            None => return Ok(Self::default()),
+            Some(_) => {
+                return Err(IError::new(
+                    GenericErrorKind::InvalidFormat,
+                    String::from("`suppress` must be an array of tables."),
+                ))
+            }
+        };
+
+        let entries = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| match entry {
+                Value::Table(table) => Suppression::new(index, table, registry),
+                _ => Err(IError::new(
+                    GenericErrorKind::InvalidFormat,
+                    format!("Suppression #{}: not a table.", index + 1),
+                )),
+            })
+            .collect::<Result<Vec<Suppression>, IError>>()?;
+
+        Ok(Self { entries })
+    }
+
+    pub fn entries(&self) -> &Vec<Suppression> {
+        &self.entries
+    }
+
+    pub fn is_suppressed(&self, rule: &str, class_name: &str, fail: &Fail) -> bool {
+        self.entries.iter().any(|entry| entry.covers(rule, class_name, fail))
+    }
+
+    /// The indices of the entries covering a fail, all of them so that none
+    /// is reported as unused.
+    pub fn covering(&self, rule: &str, class_name: &str, fail: &Fail) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.covers(rule, class_name, fail))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const FILES: &str = "tests/inputs/config/suppressions";
+
+    fn fail(method: &str, descriptor: &str) -> Fail {
+        Fail::new(
+            String::from(method),
+            String::from("message"),
+            GenericErrorKind::RuleCheckFailed,
+        )
+        .with_descriptor(descriptor)
+    }
+
+    #[rstest]
+    #[case::valid("valid.toml", true)]
+    #[case::missing_justification("missing_justification.toml", false)]
+    #[case::unknown_rule("unknown_rule.toml", false)]
+    #[case::missing_file("does_not_exist.toml", false)]
+    fn test_read(#[case] file: &str, #[case] expected: bool) {
+        let result = SuppressionFile::read(format!("{}/{}", FILES, file).as_str(), &Registry::default());
+        assert_eq!(result.is_ok(), expected);
+    }
+
+    #[rstest]
+    #[case::class_glob("check_no_void", "com.example.generated.Foo", "anything", "()V", true)]
+    #[case::class_glob_depth("check_no_void", "com.example.generated.sub.Foo", "anything", "()V", false)]
+    #[case::other_rule("no_binary_in_names", "com.example.generated.Foo", "anything", "()V", false)]
+    #[case::method_name("no_binary_in_names", "com.example.Api", "readAndWrite", "()V", true)]
+    #[case::method_name_mismatch("no_binary_in_names", "com.example.Api", "readOrWrite", "()V", false)]
+    #[case::signature("too_many_arguments", "com.example.Builder", "of", "(IIIII)V", true)]
+    #[case::signature_mismatch("too_many_arguments", "com.example.Builder", "of", "(JJJJJ)V", false)]
+    fn test_is_suppressed(
+        #[case] rule: &str,
+        #[case] class_name: &str,
+        #[case] method: &str,
+        #[case] descriptor: &str,
+        #[case] expected: bool,
+    ) {
+        let suppressions = SuppressionFile::read(format!("{}/valid.toml", FILES).as_str(), &Registry::default()).unwrap();
+        let fail = fail(method, descriptor);
+        assert_eq!(suppressions.is_suppressed(rule, class_name, &fail), expected);
+    }
+
+    #[rstest]
+    #[case::one("check_no_void", "com.example.generated.Foo", "run", "()V", vec![0])]
+    #[case::none("check_no_void", "com.example.Foo", "run", "()V", vec![])]
+    #[case::signature("too_many_arguments", "com.example.Builder", "of", "(IIIII)V", vec![2])]
+    fn test_covering(
+        #[case] rule: &str,
+        #[case] class_name: &str,
+        #[case] method: &str,
+        #[case] descriptor: &str,
+        #[case] expected: Vec<usize>,
+    ) {
+        let suppressions = SuppressionFile::read(format!("{}/valid.toml", FILES).as_str(), &Registry::default()).unwrap();
+        assert_eq!(suppressions.covering(rule, class_name, &fail(method, descriptor)), expected);
+    }
+}
@@ -1,23 +1,83 @@
 use colored::Colorize;
-use super::generic::GenericErrorKind;
+use super::generic::{GenericErrorKind, IError};
+use crate::rules::java::descriptor::MethodType;
+use std::error::Error;
 use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct Fail {
     method: String,
+    descriptor: Option<String>,
+    /// The name of the method in the source language, when it differs, e.g.
+    /// `property foo (getter)` for `getFoo` in Kotlin.
+    source_name: Option<String>,
     message: String,
     kind: GenericErrorKind,
+    /// The error the rule ran into, when the fail is not a finding.
+    source: Option<Box<IError>>,
 }
 
 impl Fail {
+    /// * `method`: the failing method, empty when the fail is about a whole class or package
     pub fn new(method: String, message: String, kind: GenericErrorKind) -> Self {
-        Self {method, message, kind}
+        Self {method, descriptor: None, source_name: None, message, kind, source: None}
+    }
+
+    /// A fail for an error a rule ran into while checking a method, keeping
+    /// the error as its source.
+    ///
+    /// * `method`: the method being checked
+    /// * `error`: the error
+    pub fn from_error(method: String, error: IError) -> Self {
+        Self {
+            method,
+            descriptor: None,
+            source_name: None,
+            message: error.full_message(),
+            kind: error.kind(),
+            source: Some(Box::new(error)),
+        }
+    }
+
+    /// Attach the descriptor of the failing method, telling overloads apart.
+    pub fn with_descriptor(mut self, descriptor: &str) -> Self {
+        self.descriptor = Some(String::from(descriptor));
+        self
+    }
+
+    /// Attach the class file being checked to the error of the fail, if any.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.source = self.source.map(|error| Box::new(error.with_path(path)));
+        self
+    }
+
+    /// Attach the name the failing method has in the source language.
+    pub fn with_source_name(mut self, source_name: String) -> Self {
+        self.source_name = Some(source_name);
+        self
     }
 
     pub fn method(&self) -> &String {
         &self.method
     }
 
+    pub fn descriptor(&self) -> Option<&String> {
+        self.descriptor.as_ref()
+    }
+
+    /// The Java signature of the method, e.g. `void run(int)`, its name in
+    /// the source language when it differs, or its name when the descriptor
+    /// is missing or invalid.
+    pub fn signature(&self) -> String {
+        if let Some(source_name) = &self.source_name {
+            return source_name.to_owned();
+        }
+        self.descriptor
+            .as_deref()
+            .and_then(|descriptor| MethodType::parse(descriptor).ok())
+            .map_or_else(|| self.method.to_owned(), |method_type| method_type.signature(&self.method))
+    }
+
     pub fn message(&self) -> &String {
         &self.message
     }
@@ -25,10 +85,24 @@ impl Fail {
     pub fn kind(&self) -> GenericErrorKind {
         self.kind
     }
+
+    /// The error the rule ran into, `None` for findings.
+    pub fn error(&self) -> Option<&IError> {
+        self.source.as_deref()
+    }
 }
 
 impl fmt::Display for Fail {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(format!("(method: {}) - error: {}, trace: {}", self.method(), self.kind().to_string().red().bold(), self.message()).as_str())
+        if !self.method.is_empty() {
+            write!(f, "(method: {}) - ", self.signature())?;
+        }
+        f.write_str(format!("error: {}, trace: {}", self.kind().to_string().red().bold(), self.message()).as_str())
     }
-}
\ No newline at end of file
+}
+
+impl Error for Fail {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|source| source as &(dyn Error + 'static))
+    }
+}
@@ -1,15 +1,33 @@
+use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum GenericErrorKind {
     ParseError,
     NotFound,
     InvalidFormat,
     RuleCheckFailed,
     InvalidPath,
+    /// An invalid configuration file, suppressions file or command line.
+    Config,
     Other,
 }
 
+impl GenericErrorKind {
+    /// The stage an error of this kind fails in, unless told otherwise.
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            GenericErrorKind::ParseError | GenericErrorKind::InvalidFormat | GenericErrorKind::NotFound => {
+                ErrorCategory::Parse
+            }
+            GenericErrorKind::InvalidPath | GenericErrorKind::Other => ErrorCategory::Input,
+            GenericErrorKind::RuleCheckFailed => ErrorCategory::Rule,
+            GenericErrorKind::Config => ErrorCategory::Config,
+        }
+    }
+}
+
 impl fmt::Display for GenericErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -18,20 +36,89 @@ impl fmt::Display for GenericErrorKind {
             GenericErrorKind::InvalidFormat => write!(f, "Invalid Format"),
             GenericErrorKind::InvalidPath => write!(f, "Invalid Path"),
             GenericErrorKind::RuleCheckFailed => write!(f, "Rule Check Failed"),
+            GenericErrorKind::Config => write!(f, "Configuration Error"),
             GenericErrorKind::Other => write!(f, "Other"),
         }
     }
 }
 
+/// The stage of a run an error happened in, ordered from the least to the
+/// most fundamental, each with its own exit code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ErrorCategory {
+    /// A rule could not be evaluated on a class.
+    Rule,
+    /// A class file is malformed.
+    Parse,
+    /// A file, directory or archive could not be read or written.
+    Input,
+    /// The configuration, the suppressions file or the command line is invalid.
+    Config,
+}
+
+impl ErrorCategory {
+    /// The exit code of the linter, 1 being left for the fails of the rules.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCategory::Config => 2,
+            ErrorCategory::Input => 3,
+            ErrorCategory::Parse => 4,
+            ErrorCategory::Rule => 5,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorCategory::Config => f.write_str("configuration"),
+            ErrorCategory::Input => f.write_str("input"),
+            ErrorCategory::Parse => f.write_str("parse"),
+            ErrorCategory::Rule => f.write_str("rule"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IError {
     kind: GenericErrorKind,
     message: String,
+    category: ErrorCategory,
+    /// The file the error is about.
+    path: Option<String>,
+    /// The byte the error is at, in the file or in the attribute being read.
+    offset: Option<usize>,
+    /// The error of the library or the system this error comes from.
+    source: Option<Arc<dyn Error + Send + Sync>>,
 }
 
 impl IError {
     pub fn new(kind: GenericErrorKind, message: String) -> Self {
-        Self { kind, message }
+        Self { kind, message, category: kind.category(), path: None, offset: None, source: None }
+    }
+
+    /// Move the error to another stage than the one of its kind, e.g. a
+    /// suppressions file failing to parse is a configuration error.
+    pub fn with_category(mut self, category: ErrorCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Attach the file the error is about, keeping the innermost one.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path.get_or_insert_with(|| path.to_owned());
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Attach the error this error comes from, returned by `source()`.
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
     }
 
     pub fn kind(&self) -> GenericErrorKind {
@@ -41,11 +128,72 @@ impl IError {
     pub fn message(&self) -> &String {
         &self.message
     }
+
+    pub fn category(&self) -> ErrorCategory {
+        self.category
+    }
+
+    pub fn path(&self) -> Option<&String> {
+        self.path.as_ref()
+    }
+
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// The message followed by the messages of the chain of sources, e.g.
+    /// `Cannot read Foo.class: No such file or directory (os error 2)`.
+    pub fn full_message(&self) -> String {
+        let mut message = self.message.to_owned();
+        let mut source = self.source();
+        while let Some(error) = source {
+            message.push_str(format!(": {}", error).as_str());
+            source = error.source();
+        }
+        message
+    }
 }
 
 impl fmt::Display for IError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let string  = format!("error: {}, trace: {}", self.kind(), self.message());
-        f.write_str(string.as_str())
+        write!(f, "error: {}, trace: {}", self.kind(), self.message())?;
+        if let Some(path) = &self.path {
+            write!(f, ", file: {}", path)?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, ", at byte: {}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for IError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|source| source as &(dyn Error + 'static))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn test_source_chain() {
+        let io_error = io::Error::new(io::ErrorKind::NotFound, "no such file");
+        let error = IError::new(GenericErrorKind::InvalidPath, String::from("Cannot read Foo.class"))
+            .with_path("Foo.class")
+            .with_source(io_error);
+        assert_eq!(error.source().map(ToString::to_string).as_deref(), Some("no such file"));
+        assert_eq!(error.full_message(), "Cannot read Foo.class: no such file");
+        assert_eq!(error.to_string(), "error: Invalid Path, trace: Cannot read Foo.class, file: Foo.class");
+    }
+
+    #[test]
+    fn test_category() {
+        let error = IError::new(GenericErrorKind::InvalidFormat, String::from("Unknown rule")).with_offset(12);
+        assert_eq!(error.category(), ErrorCategory::Parse);
+        assert_eq!(error.with_category(ErrorCategory::Config).category().exit_code(), 2);
+        assert!(ErrorCategory::Config > ErrorCategory::Rule);
+    }
+}
@@ -0,0 +1,19 @@
+//! Lint compiled Java classes for patterns that make code hard to unit test.
+//!
+//! The [`Linter`] runs the rules of a [`Registry`], configured by a
+//! [`config::config::Config`], over [`Input`]s and returns [`Diagnostics`],
+//! which [`Reporter`]s write out as console, JSON or SARIF reports.
+
+pub mod config;
+pub mod errors;
+pub mod linter;
+pub mod report;
+pub mod rules;
+pub mod types;
+pub mod utils;
+
+pub use linter::input::Input;
+pub use linter::linter::{Diagnostics, Linter, LinterBuilder};
+pub use report::reporter::Reporter;
+pub use rules::registry::Registry;
+pub use types::diagnostic::Diagnostic;
@@ -0,0 +1,133 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use crate::errors::generic::*;
+use crate::rules::java::class::JavaClass;
+use crate::utils::path::find_class_files;
+
+/// A class, or the location it could not be read from along with why.
+pub type ReadClass = Result<JavaClass, (String, IError)>;
+
+/// Where the linter reads classes from.
+#[derive(Debug, Clone)]
+pub enum Input {
+    /// A class file, a directory searched for class files, or a `.jar`/`.zip` archive.
+    Path(PathBuf),
+    /// The content of a single class file, reported under `name`.
+    Bytes { name: String, bytes: Vec<u8> },
+    /// A `.jar` or `.zip` archive, whatever its extension.
+    Archive(PathBuf),
+}
+
+impl Input {
+    /// Read and parse every class of the input.
+    pub fn classes(&self) -> Vec<ReadClass> {
+        match self {
+            Input::Path(path) if path.is_dir() => match find_class_files(path) {
+                Ok(files) => files.iter().map(read_class_file).collect(),
+                Err(e) => vec![Err((path.display().to_string(), e))],
+            },
+            Input::Path(path) if is_archive(path) => read_archive(path),
+            Input::Path(path) => vec![read_class_file(&path.display().to_string())],
+            Input::Bytes { name, bytes } => {
+                vec![JavaClass::from_bytes(bytes, name.to_owned()).map_err(|e| (name.to_owned(), e.with_path(name)))]
+            }
+            Input::Archive(path) => read_archive(path),
+        }
+    }
+}
+
+fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "jar" || extension == "zip")
+}
+
+fn read_class_file(file: &String) -> ReadClass {
+    JavaClass::parse(file).map_err(|e| (file.to_owned(), e.with_path(file)))
+}
+
+/// Read the class files of an archive, named `<archive>!/<entry>` in reports.
+fn read_archive(path: &Path) -> Vec<ReadClass> {
+    let name = path.display().to_string();
+    let mut archive = match File::open(path) {
+        Ok(file) => match ZipArchive::new(file) {
+            Ok(archive) => archive,
+            Err(e) => return vec![Err((name.to_owned(), archive_error(&name, e)))],
+        },
+        Err(e) => {
+            let error = IError::new(GenericErrorKind::InvalidPath, format!("Cannot open {}", name));
+            return vec![Err((name.to_owned(), error.with_path(&name).with_source(e)))];
+        }
+    };
+
+    (0..archive.len())
+        .filter_map(|index| {
+            let mut entry = match archive.by_index(index) {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err((name.to_owned(), archive_error(&name, e)))),
+            };
+            if !entry.name().ends_with(".class") {
+                return None;
+            }
+
+            let file = format!("{}!/{}", name, entry.name());
+            let mut bytes = Vec::new();
+            if let Err(e) = entry.read_to_end(&mut bytes) {
+                let error = IError::new(GenericErrorKind::InvalidFormat, format!("Cannot extract {}", file));
+                return Some(Err((file.to_owned(), error.with_path(&file).with_source(e))));
+            }
+            Some(JavaClass::from_bytes(&bytes, file.to_owned()).map_err(|e| (file.to_owned(), e.with_path(&file))))
+        })
+        .collect()
+}
+
+fn archive_error(name: &str, error: ZipError) -> IError {
+    IError::new(GenericErrorKind::ParseError, format!("Invalid archive {}", name))
+        .with_category(ErrorCategory::Input)
+        .with_path(name)
+        .with_source(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::fs;
+
+    #[rstest]
+    #[case::class_file("tests/inputs/java/check_no_void/valid/MainMethod.class", 1)]
+    #[case::directory("tests/inputs/java/too_many_arguments", 2)]
+    #[case::archive("tests/inputs/java/archive/classes.jar", 3)]
+    fn test_path(#[case] path: &str, #[case] expected: usize) {
+        let classes = Input::Path(PathBuf::from(path)).classes();
+        assert_eq!(classes.len(), expected);
+        assert!(classes.iter().all(|class| class.is_ok()));
+    }
+
+    #[test]
+    fn test_archive_entry_names() {
+        let classes = Input::Archive(PathBuf::from("tests/inputs/java/archive/classes.jar")).classes();
+        let class = classes.iter().find_map(|class| class.as_ref().ok()).unwrap();
+        assert!(class.file().starts_with("tests/inputs/java/archive/classes.jar!/"));
+    }
+
+    #[test]
+    fn test_bytes() {
+        let bytes = fs::read("tests/inputs/java/check_no_void/valid/MainMethod.class").unwrap();
+        let classes = Input::Bytes { name: String::from("MainMethod"), bytes }.classes();
+        assert_eq!(classes[0].as_ref().unwrap().name(), "MainMethod");
+    }
+
+    #[rstest]
+    #[case::missing_file(Input::Path(PathBuf::from("tests/inputs/java/Missing.class")))]
+    #[case::not_an_archive(Input::Archive(PathBuf::from("tests/inputs/java/check_no_void/valid/MainMethod.java")))]
+    #[case::not_a_class(Input::Bytes { name: String::from("empty"), bytes: vec![0xCA, 0xFE] })]
+    fn test_unreadable(#[case] input: Input) {
+        let classes = input.classes();
+        assert_eq!(classes.len(), 1);
+        assert!(classes[0].is_err());
+    }
+}
@@ -0,0 +1,585 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::config::config::Config;
+use crate::config::suppressions::{Suppression, SuppressionFile};
+use crate::errors::generic::*;
+use crate::rules::java::class::JavaClass;
+use crate::rules::java::hierarchy::ClassIndex;
+use crate::rules::java::project::Project;
+use crate::rules::java::suppress::Suppressions;
+use crate::rules::registry::Registry;
+use crate::types::diagnostic::Diagnostic;
+use crate::types::rule::{ConfiguredRule, Phase, RuleResult, Severity, PROJECT_FILE};
+
+use super::input::Input;
+
+/// Runs the configured rules over inputs, without printing anything.
+///
+/// ```no_run
+/// use testability_linter::{config::config::Config, Input, Linter, Registry};
+///
+/// let registry = Registry::default();
+/// let table = "check_no_void = true".parse().unwrap();
+/// let config = Config::new(table, &registry).unwrap();
+/// let linter = Linter::builder().registry(registry).config(config).build().unwrap();
+/// let diagnostics = linter.lint(&[Input::Path("target/classes".into())]);
+/// for diagnostic in diagnostics.diagnostics() {
+///     println!("{}", diagnostic);
+/// }
+/// ```
+pub struct Linter {
+    config: Config,
+    registry: Registry,
+    suppression_file: SuppressionFile,
+    classpath: Vec<JavaClass>,
+}
+
+#[derive(Default)]
+pub struct LinterBuilder {
+    config: Option<Config>,
+    registry: Option<Registry>,
+}
+
+impl LinterBuilder {
+    /// The rules known to the linter, `Registry::default()` if not set.
+    pub fn registry(mut self, registry: Registry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Build the linter, reading the suppressions file and the classpath of
+    /// the configuration.
+    pub fn build(self) -> Result<Linter, IError> {
+        let Some(config) = self.config else {
+            return Err(IError::new(
+                GenericErrorKind::Config,
+                String::from("The linter has no configuration."),
+            ));
+        };
+        let registry = self.registry.unwrap_or_default();
+        let suppression_file = match config.suppressions_file() {
+            Some(file) => SuppressionFile::read(file, &registry)?,
+            None => SuppressionFile::default(),
+        };
+
+        let classpath = config
+            .classpath()
+            .iter()
+            .flat_map(|entry| Input::Path(PathBuf::from(entry)).classes())
+            .map(|class| {
+                class.map_err(|(file, e)| {
+                    IError::new(e.kind(), format!("Invalid classpath entry {}", file))
+                        .with_category(ErrorCategory::Config)
+                        .with_path(&file)
+                        .with_source(e)
+                })
+            })
+            .collect::<Result<Vec<JavaClass>, IError>>()?;
+
+        Ok(Linter { config, registry, suppression_file, classpath })
+    }
+}
+
+impl Linter {
+    pub fn builder() -> LinterBuilder {
+        LinterBuilder::default()
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Read every input and run the configured rules on its classes.
+    pub fn lint(&self, inputs: &[Input]) -> Diagnostics {
+        let mut input_errors = Vec::new();
+        let classes: Vec<JavaClass> = inputs
+            .iter()
+            .flat_map(|input| input.classes())
+            .filter_map(|class| match class {
+                Ok(class) => Some(class),
+                Err(error) => {
+                    input_errors.push(error);
+                    None
+                }
+            })
+            .collect();
+
+        let mut index = ClassIndex::new(&classes);
+        index.add(&self.classpath);
+        let index = Arc::new(index);
+        let classes: Vec<JavaClass> = classes
+            .into_iter()
+            .filter(|class| self.config.checks(class))
+            .map(|class| class.with_index(index.clone()))
+            .collect();
+
+        let suppressions: Vec<Suppressions> = classes
+            .iter()
+            .map(|class| {
+                Suppressions::new(class.class_file(), self.config.suppress_annotations())
+                    .unwrap_or_else(|e| {
+                        input_errors.push((class.file().to_owned(), e));
+                        Suppressions::default()
+                    })
+            })
+            .collect();
+
+        // How many fails each entry of the suppressions file covers.
+        let mut suppression_matches = vec![0; self.suppression_file.entries().len()];
+        let mut results = Vec::new();
+        let project = Project::new(&classes, &index);
+        let annotations: HashMap<&String, &Suppressions> =
+            classes.iter().map(JavaClass::name).zip(&suppressions).collect();
+        let mut unanalysed: Vec<Diagnostic> = input_errors
+            .iter()
+            .map(|(file, e)| Diagnostic::unanalysed(file.to_owned(), String::new(), None, e))
+            .collect();
+        self.config.rules().iter().for_each(|rule| {
+            let (rule_results, panics) = run_isolated(rule, &project);
+            unanalysed.extend(panics);
+            rule_results.into_iter().for_each(|result| {
+                // The errors a rule ran into are no findings: the class could not be analysed.
+                let (result, errors) = result.split_errors();
+                unanalysed.extend(errors.iter().map(|error| {
+                    Diagnostic::unanalysed(result.file().to_owned(), result.class().to_owned(), Some(rule), error)
+                }));
+                // Like a panic, an error leaves no result to pass.
+                if !errors.is_empty() && result.result().is_ok() {
+                    return;
+                }
+                let class_name = result.class().to_owned();
+                let annotations = annotations.get(&class_name);
+                results.push(
+                    result
+                        .suppress(|fail| annotations.is_some_and(|annotations| annotations.is_suppressed(rule.id(), fail)))
+                        .suppress(|fail| {
+                            let covering = self.suppression_file.covering(rule.id(), &class_name, fail);
+                            covering.iter().for_each(|index| suppression_matches[*index] += 1);
+                            !covering.is_empty()
+                        }),
+                );
+            });
+        });
+
+        Diagnostics {
+            results,
+            files: classes.len(),
+            rules: self.config.rules().len(),
+            input_errors,
+            unanalysed,
+            suppressions: self.suppression_file.entries().iter().cloned().zip(suppression_matches).collect(),
+        }
+    }
+}
+
+/// Everything a lint run found.
+#[derive(Debug)]
+pub struct Diagnostics {
+    results: Vec<RuleResult>,
+    files: usize,
+    rules: usize,
+    input_errors: Vec<(String, IError)>,
+    unanalysed: Vec<Diagnostic>,
+    /// The entries of the suppressions file, with the number of fails each covered.
+    suppressions: Vec<(Suppression, usize)>,
+}
+
+impl Diagnostics {
+    /// The result of every rule on every class, in the order they ran.
+    pub fn results(&self) -> &Vec<RuleResult> {
+        &self.results
+    }
+
+    /// Every fail that was not suppressed.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.results
+            .iter()
+            .flat_map(|result| {
+                let fails = result.result().as_ref().err().cloned().unwrap_or_default();
+                fails.into_iter().map(move |fail| Diagnostic::new(result, &fail))
+            })
+            .collect()
+    }
+
+    /// The number of classes checked.
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    /// The number of rules run on each class.
+    pub fn rules(&self) -> usize {
+        self.rules
+    }
+
+    pub fn failures(&self) -> usize {
+        self.results
+            .iter()
+            .map(|result| result.result().as_ref().err().map_or(0, Vec::len))
+            .sum()
+    }
+
+    pub fn suppressed(&self) -> usize {
+        self.results.iter().map(RuleResult::suppressed).sum()
+    }
+
+    /// Whether a rule of `error` severity failed.
+    pub fn has_errors(&self) -> bool {
+        self.results
+            .iter()
+            .any(|result| result.severity() == Severity::Error && result.result().is_err())
+    }
+
+    /// The inputs, or parts of them, that could not be read, with why.
+    pub fn input_errors(&self) -> &Vec<(String, IError)> {
+        &self.input_errors
+    }
+
+    /// The files that could not be read and the classes a rule panicked on or
+    /// ran into an error on, as diagnostics.
+    pub fn unanalysed(&self) -> &Vec<Diagnostic> {
+        &self.unanalysed
+    }
+
+    /// The most fundamental stage an analysis failed in, `None` when every
+    /// file was analysed.
+    pub fn error_category(&self) -> Option<ErrorCategory> {
+        self.unanalysed.iter().filter_map(Diagnostic::category).max()
+    }
+
+    /// The entries of the suppressions file, with the number of fails each covered.
+    pub fn suppressions(&self) -> &Vec<(Suppression, usize)> {
+        &self.suppressions
+    }
+
+    /// The entries of the suppressions file that matched nothing.
+    pub fn unused_suppressions(&self) -> Vec<&Suppression> {
+        self.suppressions
+            .iter()
+            .filter(|(_, matches)| *matches == 0)
+            .map(|(suppression, _)| suppression)
+            .collect()
+    }
+}
+
+/// Run a rule on every class of a project, catching its panics class by
+/// class so that a single class cannot abort the whole run.
+fn run_isolated(rule: &ConfiguredRule, project: &Project) -> (Vec<RuleResult>, Vec<Diagnostic>) {
+    let mut results = Vec::new();
+    let mut panics = Vec::new();
+    let mut run = |file: &str, class: &str, run_rule: &dyn Fn() -> Vec<RuleResult>| {
+        match panic::catch_unwind(AssertUnwindSafe(run_rule)) {
+            Ok(rule_results) => results.extend(rule_results),
+            Err(payload) => {
+                let message = format!("the rule panicked: {}", panic_message(payload.as_ref()));
+                let error = IError::new(GenericErrorKind::RuleCheckFailed, message).with_path(file);
+                panics.push(Diagnostic::unanalysed(file.to_owned(), class.to_owned(), Some(rule), &error));
+            }
+        }
+    };
+
+    match rule.rule().phase() {
+        Phase::Class => project
+            .classes()
+            .iter()
+            .for_each(|class| run(class.file(), class.name(), &|| vec![rule.run(class)])),
+        Phase::Project => run(PROJECT_FILE, "", &|| rule.run_project(project)),
+    }
+    (results, panics)
+}
+
+/// The message a panic was raised with.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message,
+        (_, Some(message)) => message,
+        _ => "unknown cause",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::fail::Fail;
+    use crate::types::diagnostic::UNANALYSED_RULE;
+    use crate::types::rule::{Rule, RuleOptions};
+    use rstest::rstest;
+    use toml::Table;
+
+    fn linter_for_test(toml: &str) -> Linter {
+        let registry = Registry::default();
+        let config = Config::new(toml.parse::<Table>().unwrap(), &registry).unwrap();
+        Linter::builder().registry(registry).config(config).build().unwrap()
+    }
+
+    #[test]
+    fn test_lint() {
+        let linter = linter_for_test("check_no_void = true\ntoo_many_arguments = 4");
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/too_many_arguments"))]);
+        assert_eq!(diagnostics.files(), 2);
+        assert_eq!(diagnostics.rules(), 2);
+        assert_eq!(diagnostics.results().len(), 4);
+        assert_eq!(diagnostics.failures(), 7);
+        assert_eq!(diagnostics.diagnostics().len(), 7);
+        assert!(diagnostics.has_errors());
+    }
+
+    #[test]
+    fn test_lint_warning_only() {
+        let linter = linter_for_test("[check_no_void]\nseverity = \"warning\"");
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/too_many_arguments"))]);
+        assert_eq!(diagnostics.failures(), 6);
+        assert!(!diagnostics.has_errors());
+    }
+
+    #[test]
+    fn test_lint_suppressions() {
+        let linter = linter_for_test("check_no_void = true");
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/suppress/SuppressedMethod.class"))]);
+        assert_eq!(diagnostics.failures(), 2);
+        assert_eq!(diagnostics.suppressed(), 2);
+    }
+
+    #[test]
+    fn test_lint_suppressions_file() {
+        let linter = linter_for_test(
+            "suppressions_file = \"tests/inputs/config/suppressions/lint.toml\"\ncheck_no_void = true",
+        );
+        let inputs = [Input::Path(PathBuf::from("tests/inputs/java/too_many_arguments"))];
+        // The counts of a run don't carry over to the next one.
+        for _ in 0..2 {
+            let diagnostics = linter.lint(&inputs);
+            assert_eq!(diagnostics.suppressed(), 6);
+            let matches: Vec<usize> = diagnostics.suppressions().iter().map(|(_, matches)| *matches).collect();
+            assert_eq!(matches, vec![6, 0]);
+            assert_eq!(diagnostics.unused_suppressions().len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_linter_is_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<Linter>();
+    }
+
+    #[test]
+    fn test_lint_input_errors() {
+        let linter = linter_for_test("check_no_void = true");
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/Missing.class"))]);
+        assert_eq!(diagnostics.files(), 0);
+        assert_eq!(diagnostics.input_errors().len(), 1);
+        let unanalysed = &diagnostics.unanalysed()[0];
+        assert_eq!(unanalysed.file(), "tests/inputs/java/Missing.class");
+        assert_eq!(unanalysed.rule(), UNANALYSED_RULE);
+        assert_eq!(unanalysed.severity(), Severity::Error);
+        assert_eq!(diagnostics.error_category(), Some(ErrorCategory::Input));
+    }
+
+    struct PanickingRule;
+
+    impl Rule for PanickingRule {
+        fn id(&self) -> &'static str {
+            "panicking"
+        }
+
+        fn name(&self) -> &'static str {
+            "Panicking"
+        }
+
+        fn description(&self) -> &'static str {
+            "A rule panicking on one class."
+        }
+
+        fn rationale(&self) -> &'static str {
+            "Tests."
+        }
+
+        fn check(&self, class: &JavaClass, _options: &RuleOptions) -> Vec<Fail> {
+            match class.name().as_str() {
+                "TooManyArguments" => panic!("unexpected class"),
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_lint_rule_panic() {
+        let mut registry = Registry::default();
+        registry.register(Arc::new(PanickingRule));
+        let config = Config::new("panicking = true\ncheck_no_void = true".parse::<Table>().unwrap(), &registry).unwrap();
+        let linter = Linter::builder().registry(registry).config(config).build().unwrap();
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/too_many_arguments"))]);
+
+        assert_eq!(diagnostics.unanalysed().len(), 1);
+        let unanalysed = &diagnostics.unanalysed()[0];
+        assert_eq!(unanalysed.rule(), "panicking");
+        assert_eq!(unanalysed.class(), "TooManyArguments");
+        assert_eq!(unanalysed.message(), "could not analyse: the rule panicked: unexpected class");
+        assert_eq!(diagnostics.error_category(), Some(ErrorCategory::Rule));
+        assert_eq!(diagnostics.results().iter().filter(|result| result.rule() == "check_no_void").count(), 2);
+    }
+
+    /// A rule running into an error of its kind on every class.
+    struct ErroringRule(GenericErrorKind);
+
+    impl Rule for ErroringRule {
+        fn id(&self) -> &'static str {
+            "erroring"
+        }
+
+        fn name(&self) -> &'static str {
+            "Erroring"
+        }
+
+        fn description(&self) -> &'static str {
+            "A rule running into an error."
+        }
+
+        fn rationale(&self) -> &'static str {
+            "Tests."
+        }
+
+        fn check(&self, _class: &JavaClass, _options: &RuleOptions) -> Vec<Fail> {
+            vec![Fail::from_error(String::from("run"), IError::new(self.0, String::from("cannot evaluate")))]
+        }
+    }
+
+    #[rstest]
+    #[case::rule(GenericErrorKind::RuleCheckFailed, ErrorCategory::Rule, 5)]
+    #[case::parse(GenericErrorKind::InvalidFormat, ErrorCategory::Parse, 4)]
+    fn test_lint_rule_error(#[case] kind: GenericErrorKind, #[case] expected: ErrorCategory, #[case] exit_code: i32) {
+        let mut registry = Registry::default();
+        registry.register(Arc::new(ErroringRule(kind)));
+        let config = Config::new("erroring = true
+check_no_void = true".parse::<Table>().unwrap(), &registry).unwrap();
+        let linter = Linter::builder().registry(registry).config(config).build().unwrap();
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/too_many_arguments"))]);
+
+        assert_eq!(diagnostics.unanalysed().len(), 2);
+        assert!(diagnostics.unanalysed().iter().all(|diagnostic| diagnostic.rule() == "erroring"));
+        assert!(diagnostics.diagnostics().iter().all(|diagnostic| diagnostic.rule() == "check_no_void"));
+        assert_eq!(diagnostics.error_category(), Some(expected));
+        assert_eq!(diagnostics.error_category().map(ErrorCategory::exit_code), Some(exit_code));
+    }
+
+    #[test]
+    fn test_build_without_config() {
+        assert!(Linter::builder().build().is_err());
+    }
+
+    #[rstest]
+    #[case::alone("", &["Impl.class"], 5)]
+    #[case::with_supertypes("", &["Impl.class", "Base.class", "Listener.class"], 5)]
+    #[case::classpath("classpath = [\"tests/inputs/java/hierarchy/lib/supertypes.jar\"]\n", &["Impl.class"], 2)]
+    fn test_lint_hierarchy(#[case] classpath: &str, #[case] files: &[&str], #[case] expected: usize) {
+        let linter = linter_for_test(format!("{}check_no_void = true", classpath).as_str());
+        let inputs: Vec<Input> = files
+            .iter()
+            .map(|file| Input::Path(PathBuf::from(format!("tests/inputs/java/hierarchy/{}", file))))
+            .collect();
+        assert_eq!(linter.lint(&inputs).failures(), expected);
+    }
+
+    #[test]
+    fn test_lint_project_phase() {
+        let linter = linter_for_test("package_cycles = true");
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/package_cycles"))]);
+        assert_eq!(diagnostics.files(), 3);
+        assert_eq!(diagnostics.results().len(), 2);
+        let classes: Vec<String> = diagnostics.diagnostics().iter().map(|diagnostic| diagnostic.class().to_owned()).collect();
+        assert_eq!(classes, vec!["a", "b"]);
+    }
+
+    #[rstest]
+    #[case::all("", 8)]
+    #[case::no_anonymous("anonymous_classes = false\n", 5)]
+    #[case::no_synthetic("synthetic_classes = false\n", 7)]
+    fn test_lint_nested_classes(#[case] settings: &str, #[case] expected: usize) {
+        let linter = linter_for_test(format!("{}check_no_void = true", settings).as_str());
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/nesting"))]);
+        assert_eq!(diagnostics.files(), expected);
+    }
+
+    #[test]
+    fn test_lint_nested_location() {
+        let linter = linter_for_test("check_no_void = true");
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/nesting/Outer$2.class"))]);
+        let diagnostic = &diagnostics.diagnostics()[0];
+        assert_eq!(diagnostic.class(), "Outer$2");
+        assert_eq!(diagnostic.source_class(), "Outer");
+        assert_eq!(diagnostic.location(), "anonymous class in `Outer.bar(int)`");
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: anonymous class in `Outer.bar(int)`: void reset(): This method has return type of void [check_no_void]"
+        );
+    }
+
+    #[rstest]
+    #[case::default("check_no_void = true", vec!["void move()", "void x()"])]
+    #[case::generated(
+        "[check_no_void.scope]\ngenerated = true",
+        vec!["property y (setter)", "void move()", "void $r8$lambda$move(Point)", "void x()"]
+    )]
+    fn test_lint_kotlin(#[case] toml: &str, #[case] expected: Vec<&str>) {
+        let linter = linter_for_test(toml);
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/kotlin/Point.class"))]);
+        let signatures: Vec<String> = diagnostics.diagnostics().iter().map(|diagnostic| diagnostic.signature().to_owned()).collect();
+        assert_eq!(signatures, expected);
+    }
+
+    #[rstest]
+    #[case::default("too_many_arguments = 4", 0)]
+    #[case::implicit("[too_many_arguments]\nmax = 4\nscope = { implicit = true }", 1)]
+    fn test_lint_record(#[case] toml: &str, #[case] expected: usize) {
+        let linter = linter_for_test(toml);
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/modern/Point.class"))]);
+        assert_eq!(diagnostics.failures(), expected);
+        assert!(diagnostics.diagnostics().iter().all(|diagnostic| diagnostic.major_version() == Some(61)));
+    }
+
+    #[test]
+    fn test_lint_corrupt_constant_pool() {
+        // Wide.class with the name of its method `big` pointing at constant #0.
+        let linter = linter_for_test("check_no_void = true\nno_binary_in_names = true\ncyclomatic_complexity = 1");
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/constant_pool/Corrupt.class"))]);
+        assert!(diagnostics.diagnostics().is_empty());
+        let messages: Vec<&String> = diagnostics.unanalysed().iter().map(Diagnostic::message).collect();
+        assert_eq!(messages.len(), 3);
+        assert!(messages.iter().all(|message| message.starts_with("could not analyse: ") && message.contains("Constant #0 does not exist.")));
+        assert!(diagnostics.unanalysed().iter().all(|diagnostic| diagnostic.file() == "tests/inputs/java/constant_pool/Corrupt.class"));
+        assert_eq!(diagnostics.error_category(), Some(ErrorCategory::Parse));
+        assert!(!diagnostics.has_errors());
+    }
+
+    #[test]
+    fn test_lint_malformed_class_name() {
+        // Wide.class with its this_class pointing at constant #0.
+        let file = "tests/inputs/java/constant_pool/NoThisClass.class";
+        let linter = linter_for_test("check_no_void = true");
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from(file))]);
+        assert_eq!(diagnostics.files(), 0);
+        assert_eq!(diagnostics.input_errors().len(), 1);
+        assert_eq!(diagnostics.unanalysed()[0].file(), file);
+        assert_eq!(diagnostics.error_category(), Some(ErrorCategory::Parse));
+    }
+
+    #[test]
+    fn test_build_missing_classpath() {
+        let registry = Registry::default();
+        let table = "classpath = [\"tests/inputs/java/missing.jar\"]\ncheck_no_void = true".parse::<Table>().unwrap();
+        let config = Config::new(table, &registry).unwrap();
+        assert!(Linter::builder().registry(registry).config(config).build().is_err());
+    }
+}
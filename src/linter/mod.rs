@@ -0,0 +1,3 @@
+pub mod input;
+#[allow(clippy::module_inception)]
+pub mod linter;
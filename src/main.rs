@@ -1,33 +1,36 @@
-mod errors;
-mod types;
-mod rules;
-mod utils;
 mod cli;
-mod config;
 
 use std::env;
 use cli::cli::*;
+use testability_linter::config::schema::config_schema;
+use testability_linter::errors::generic::ErrorCategory;
+use testability_linter::Registry;
 
 
 
 fn main() {
     let args: Vec<String> = env::args().collect();
+    let registry = Registry::default();
+
+    if args.get(1).map(String::as_str) == Some("config-schema") {
+        println!("{:#}", config_schema(&registry));
+        std::process::exit(0);
+    }
+
+    if args.get(1).map(String::as_str) == Some("init") {
+        std::process::exit(init_config(&args[2..], &registry));
+    }
 
     let config_file = match args.get(1) {
         Some(file) => file.to_owned(),
-        None => { eprintln!("You didn't put a config file"); std::process::exit(1); }
+        None => { eprintln!("You didn't put a config file"); std::process::exit(ErrorCategory::Config.exit_code()); }
     };
 
-    let rules = match create_rules_list(config_file) {
-        Some(rules) => rules,
-        None => { eprintln!("You didn't select rules in the config file"); std::process::exit(1); }
+    let config = match create_config(config_file, &registry) {
+        Ok(config) => config,
+        Err(e) => std::process::exit(print_error(&e)),
     };
-    let mut code = 0;
-    rules.iter().for_each(|rule| {
-        let rule_code = lint_files(args[2..].to_vec(), rule);
-        if code != 1 { code = rule_code }
-    });
 
-    std::process::exit(code);
+    std::process::exit(lint_files(args[2..].to_vec(), config, &registry));
 }
 
@@ -0,0 +1,34 @@
+use crate::errors::generic::IError;
+use crate::linter::linter::Diagnostics;
+use crate::types::diagnostic::Diagnostic;
+use crate::types::rule::RuleResult;
+
+use super::reporter::Reporter;
+
+/// Prints every result to the standard output, followed by a summary.
+#[derive(Debug, Default)]
+pub struct ConsoleReporter;
+
+impl Reporter for ConsoleReporter {
+    fn result(&mut self, result: &RuleResult) -> Result<(), IError> {
+        println!("{}", result);
+        Ok(())
+    }
+
+    fn diagnostic(&mut self, diagnostic: &Diagnostic) -> Result<(), IError> {
+        println!("{}", diagnostic);
+        Ok(())
+    }
+
+    fn finish(&mut self, diagnostics: &Diagnostics) -> Result<(), IError> {
+        println!(
+            "Checked {} file(s) against {} rule(s): {} failure(s), {} suppressed, {} could not be analysed",
+            diagnostics.files(),
+            diagnostics.rules(),
+            diagnostics.failures(),
+            diagnostics.suppressed(),
+            diagnostics.unanalysed().len()
+        );
+        Ok(())
+    }
+}
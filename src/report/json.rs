@@ -0,0 +1,84 @@
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use crate::errors::generic::IError;
+use crate::linter::linter::Diagnostics;
+#[cfg(test)]
+use crate::linter::linter::Linter;
+use crate::types::diagnostic::Diagnostic;
+
+use super::reporter::{write_output, Reporter};
+
+/// Writes the diagnostics of a run and its summary as a JSON document.
+#[derive(Debug, Default)]
+pub struct JsonReporter {
+    output: Option<PathBuf>,
+    diagnostics: Vec<Value>,
+}
+
+impl JsonReporter {
+    /// * `output`: the file to write, the standard output if `None`
+    pub fn new(output: Option<PathBuf>) -> Self {
+        Self { output, diagnostics: Vec::new() }
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn diagnostic(&mut self, diagnostic: &Diagnostic) -> Result<(), IError> {
+        self.diagnostics.push(json!({
+            "rule": diagnostic.rule(),
+            "name": diagnostic.rule_name(),
+            "severity": diagnostic.severity().to_key(),
+            "file": diagnostic.file(),
+            "class": diagnostic.class(),
+            "source_class": diagnostic.source_class(),
+            "location": diagnostic.location(),
+            "major_version": diagnostic.major_version(),
+            "method": diagnostic.method(),
+            "descriptor": diagnostic.descriptor(),
+            "signature": diagnostic.signature(),
+            "message": diagnostic.message(),
+        }));
+        Ok(())
+    }
+
+    fn finish(&mut self, diagnostics: &Diagnostics) -> Result<(), IError> {
+        let document = json!({
+            "files": diagnostics.files(),
+            "rules": diagnostics.rules(),
+            "failures": diagnostics.failures(),
+            "suppressed": diagnostics.suppressed(),
+            "unanalysed": diagnostics.unanalysed().len(),
+            "diagnostics": std::mem::take(&mut self.diagnostics),
+        });
+        write_output(self.output.as_deref(), format!("{:#}", document).as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::config::Config;
+    use crate::linter::input::Input;
+    use crate::report::reporter::report;
+    use crate::rules::registry::Registry;
+    use std::{env, fs};
+
+    #[test]
+    fn test_json_report() {
+        let registry = Registry::default();
+        let config = Config::new("check_no_void = true".parse().unwrap(), &registry).unwrap();
+        let linter = Linter::builder().registry(registry).config(config).build().unwrap();
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/too_many_arguments"))]);
+
+        let output = env::temp_dir().join("testability-linter-json/lint.json");
+        let mut reporters: Vec<Box<dyn Reporter>> = vec![Box::new(JsonReporter::new(Some(output.clone())))];
+        report(&linter, &diagnostics, &mut reporters).unwrap();
+
+        let document: Value = serde_json::from_str(fs::read_to_string(output).unwrap().as_str()).unwrap();
+        assert_eq!(document["files"], 2);
+        assert_eq!(document["diagnostics"].as_array().unwrap().len(), 6);
+        assert_eq!(document["diagnostics"][0]["rule"], "check_no_void");
+        assert_eq!(document["diagnostics"][0]["severity"], "error");
+    }
+}
@@ -0,0 +1,4 @@
+pub mod console;
+pub mod json;
+pub mod reporter;
+pub mod sarif;
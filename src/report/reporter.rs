@@ -0,0 +1,92 @@
+use std::{fs, io, path::Path};
+
+use crate::errors::generic::*;
+use crate::linter::linter::{Diagnostics, Linter};
+use crate::types::diagnostic::Diagnostic;
+use crate::types::rule::RuleResult;
+
+/// The outputs written by a single lint run.
+pub type Reporters = Vec<Box<dyn Reporter>>;
+
+/// An output format of a lint run.
+///
+/// The hooks are called in order: `start` once, `result` for every rule run on
+/// every class, `unanalysed` for every file that could not be analysed, then
+/// `finish` once with the whole run.
+pub trait Reporter {
+    /// Called before any result, with the linter the run is made with.
+    fn start(&mut self, _linter: &Linter) -> Result<(), IError> {
+        Ok(())
+    }
+
+    /// Called for the result of a rule on a class, by default reporting each
+    /// of its diagnostics.
+    fn result(&mut self, result: &RuleResult) -> Result<(), IError> {
+        let Err(fails) = result.result() else {
+            return Ok(());
+        };
+        fails
+            .iter()
+            .try_for_each(|fail| self.diagnostic(&Diagnostic::new(result, fail)))
+    }
+
+    /// Called for every fail that was not suppressed.
+    fn diagnostic(&mut self, diagnostic: &Diagnostic) -> Result<(), IError>;
+
+    /// Called for every file that could not be read and every class a rule
+    /// panicked on, by default reporting it like a fail.
+    fn unanalysed(&mut self, diagnostic: &Diagnostic) -> Result<(), IError> {
+        self.diagnostic(diagnostic)
+    }
+
+    /// Called once every result has been reported.
+    fn finish(&mut self, _diagnostics: &Diagnostics) -> Result<(), IError> {
+        Ok(())
+    }
+}
+
+/// Feed a lint run to every reporter, stopping at the first that fails.
+///
+/// * `linter`: the linter the run was made with
+/// * `diagnostics`: what the run found
+/// * `reporters`: the outputs to write
+pub fn report(
+    linter: &Linter,
+    diagnostics: &Diagnostics,
+    reporters: &mut [Box<dyn Reporter>],
+) -> Result<(), IError> {
+    reporters.iter_mut().try_for_each(|reporter| {
+        reporter.start(linter)?;
+        diagnostics
+            .results()
+            .iter()
+            .try_for_each(|result| reporter.result(result))?;
+        diagnostics
+            .unanalysed()
+            .iter()
+            .try_for_each(|diagnostic| reporter.unanalysed(diagnostic))?;
+        reporter.finish(diagnostics)
+    })
+}
+
+/// Write a report to a file, creating its directory, or to the standard output.
+pub fn write_output(output: Option<&Path>, content: &str) -> Result<(), IError> {
+    let Some(path) = output else {
+        println!("{}", content);
+        return Ok(());
+    };
+
+    if let Some(directory) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        if let Err(e) = fs::create_dir_all(directory) {
+            return Err(write_error(directory, e));
+        }
+    }
+    fs::write(path, content).map_err(|e| write_error(path, e))
+}
+
+fn write_error(path: &Path, error: io::Error) -> IError {
+    let path = path.display().to_string();
+    IError::new(GenericErrorKind::InvalidPath, format!("Cannot write {}", path))
+        .with_path(&path)
+        .with_source(error)
+}
@@ -0,0 +1,148 @@
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use crate::errors::generic::IError;
+use crate::linter::linter::{Diagnostics, Linter};
+use crate::types::diagnostic::Diagnostic;
+
+use super::reporter::{write_output, Reporter};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Writes a SARIF 2.1.0 log, the format code scanning services import.
+#[derive(Debug, Default)]
+pub struct SarifReporter {
+    output: Option<PathBuf>,
+    rules: Vec<Value>,
+    rule_ids: Vec<&'static str>,
+    results: Vec<Value>,
+    notifications: Vec<Value>,
+}
+
+impl SarifReporter {
+    /// * `output`: the file to write, the standard output if `None`
+    pub fn new(output: Option<PathBuf>) -> Self {
+        Self { output, ..Self::default() }
+    }
+}
+
+impl Reporter for SarifReporter {
+    fn start(&mut self, linter: &Linter) -> Result<(), IError> {
+        linter.config().rules().iter().for_each(|configured| {
+            let rule = configured.rule();
+            self.rule_ids.push(rule.id());
+            self.rules.push(json!({
+                "id": rule.id(),
+                "name": rule.name(),
+                "shortDescription": { "text": rule.description() },
+                "fullDescription": { "text": rule.rationale() },
+                "defaultConfiguration": { "level": configured.severity().to_key() },
+            }));
+        });
+        Ok(())
+    }
+
+    fn diagnostic(&mut self, diagnostic: &Diagnostic) -> Result<(), IError> {
+        let mut result = json!({
+            "ruleId": diagnostic.rule(),
+            "level": diagnostic.severity().to_key(),
+            "message": { "text": diagnostic.message() },
+            "locations": [{
+                "physicalLocation": { "artifactLocation": { "uri": diagnostic.file() } },
+                "logicalLocations": [logical_location(diagnostic)],
+            }],
+        });
+        if let Some(index) = self.rule_ids.iter().position(|id| *id == diagnostic.rule()) {
+            result["ruleIndex"] = json!(index);
+        }
+        if let Some(major_version) = diagnostic.major_version() {
+            result["properties"] = json!({ "classMajorVersion": major_version });
+        }
+        self.results.push(result);
+        Ok(())
+    }
+
+    fn unanalysed(&mut self, diagnostic: &Diagnostic) -> Result<(), IError> {
+        self.notifications.push(json!({
+            "level": "error",
+            "message": { "text": diagnostic.message() },
+            "descriptor": { "id": diagnostic.rule() },
+            "locations": [{
+                "physicalLocation": { "artifactLocation": { "uri": diagnostic.file() } },
+            }],
+        }));
+        Ok(())
+    }
+
+    fn finish(&mut self, _diagnostics: &Diagnostics) -> Result<(), IError> {
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": std::mem::take(&mut self.rules),
+                    },
+                },
+                "results": std::mem::take(&mut self.results),
+                "invocations": [{
+                    "executionSuccessful": self.notifications.is_empty(),
+                    "toolExecutionNotifications": std::mem::take(&mut self.notifications),
+                }],
+            }],
+        });
+        write_output(self.output.as_deref(), format!("{:#}", log).as_str())
+    }
+}
+
+/// The method of a diagnostic, or its class when it is not about a method.
+fn logical_location(diagnostic: &Diagnostic) -> Value {
+    match diagnostic.method().is_empty() {
+        true => json!({ "fullyQualifiedName": diagnostic.class(), "kind": "type" }),
+        false => json!({
+            "fullyQualifiedName": format!("{}.{}", diagnostic.class(), diagnostic.method()),
+            "kind": "function",
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::config::Config;
+    use crate::linter::input::Input;
+    use crate::report::reporter::report;
+    use crate::rules::registry::Registry;
+    use std::{env, fs};
+
+    #[test]
+    fn test_sarif_report() {
+        let registry = Registry::default();
+        let table = "check_no_void = true\n[too_many_arguments]\nseverity = \"warning\"".parse().unwrap();
+        let config = Config::new(table, &registry).unwrap();
+        let linter = Linter::builder().registry(registry).config(config).build().unwrap();
+        let diagnostics = linter.lint(&[Input::Path(PathBuf::from("tests/inputs/java/too_many_arguments"))]);
+
+        let output = env::temp_dir().join("testability-linter-sarif/lint.sarif");
+        let mut reporters: Vec<Box<dyn Reporter>> = vec![Box::new(SarifReporter::new(Some(output.clone())))];
+        report(&linter, &diagnostics, &mut reporters).unwrap();
+
+        let log: Value = serde_json::from_str(fs::read_to_string(output).unwrap().as_str()).unwrap();
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["results"].as_array().unwrap().len(), diagnostics.failures());
+        assert_eq!(run["invocations"][0]["executionSuccessful"], true);
+        let warning = run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|result| result["ruleId"] == "too_many_arguments")
+            .unwrap();
+        assert_eq!(warning["level"], "warning");
+        let index = warning["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(run["tool"]["driver"]["rules"][index]["id"], "too_many_arguments");
+    }
+}
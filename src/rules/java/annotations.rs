@@ -0,0 +1,163 @@
+use classfile_parser::{attribute_info::AttributeInfo, constant_info::ConstantInfo};
+
+use crate::errors::generic::*;
+
+use super::reader::ByteReader;
+use super::utils::*;
+
+const VISIBLE_ANNOTATIONS: &str = "RuntimeVisibleAnnotations";
+const INVISIBLE_ANNOTATIONS: &str = "RuntimeInvisibleAnnotations";
+
+/// An annotation retained in the class file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// The field descriptor of the annotation type, e.g. `Lcom/example/Generated;`.
+    pub type_descriptor: String,
+    pub elements: Vec<(String, ElementValue)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementValue {
+    String(String),
+    Array(Vec<ElementValue>),
+    /// Constants, enums, classes and nested annotations, which no rule reads yet.
+    Other,
+}
+
+impl Annotation {
+    pub fn element(&self, name: &str) -> Option<&ElementValue> {
+        self.elements
+            .iter()
+            .find(|(element, _)| element == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl ElementValue {
+    /// The strings held by a string element or an array of strings.
+    pub fn strings(&self) -> Vec<&String> {
+        match self {
+            ElementValue::String(string) => vec![string],
+            ElementValue::Array(values) => values.iter().flat_map(|value| value.strings()).collect(),
+            ElementValue::Other => vec![],
+        }
+    }
+}
+
+/// Read the annotations, visible or not at runtime, of a class, method or field.
+///
+/// * `constant_pool`: The constant pool of the class file
+/// * `attributes`: The attributes of the annotated element
+pub fn extract_annotations(
+    constant_pool: &[ConstantInfo],
+    attributes: &[AttributeInfo],
+) -> Result<Vec<Annotation>, IError> {
+    let mut annotations = Vec::new();
+    for attribute in attributes {
+        let name = &extract_utf8_constant(constant_pool, attribute.attribute_name_index)?.utf8_string;
+        if name != VISIBLE_ANNOTATIONS && name != INVISIBLE_ANNOTATIONS {
+            continue;
+        }
+
+        let mut reader = ByteReader::new(&attribute.info);
+        for _ in 0..reader.u2()? {
+            annotations.push(parse_annotation(constant_pool, &mut reader)?);
+        }
+    }
+    Ok(annotations)
+}
+
+fn parse_annotation(
+    constant_pool: &[ConstantInfo],
+    reader: &mut ByteReader,
+) -> Result<Annotation, IError> {
+    let type_descriptor = extract_utf8_constant(constant_pool, reader.u2()?)?.utf8_string.clone();
+    let mut elements = Vec::new();
+    for _ in 0..reader.u2()? {
+        let name = extract_utf8_constant(constant_pool, reader.u2()?)?.utf8_string.clone();
+        elements.push((name, parse_element_value(constant_pool, reader)?));
+    }
+    Ok(Annotation { type_descriptor, elements })
+}
+
+fn parse_element_value(
+    constant_pool: &[ConstantInfo],
+    reader: &mut ByteReader,
+) -> Result<ElementValue, IError> {
+    match reader.u1()? {
+        b's' => {
+            let string = extract_utf8_constant(constant_pool, reader.u2()?)?;
+            Ok(ElementValue::String(string.utf8_string.clone()))
+        }
+        b'B' | b'C' | b'D' | b'F' | b'I' | b'J' | b'S' | b'Z' | b'c' => {
+            reader.u2()?;
+            Ok(ElementValue::Other)
+        }
+        b'e' => {
+            reader.u2()?;
+            reader.u2()?;
+            Ok(ElementValue::Other)
+        }
+        b'@' => {
+            parse_annotation(constant_pool, reader)?;
+            Ok(ElementValue::Other)
+        }
+        b'[' => {
+            let mut values = Vec::new();
+            for _ in 0..reader.u2()? {
+                values.push(parse_element_value(constant_pool, reader)?);
+            }
+            Ok(ElementValue::Array(values))
+        }
+        tag => Err(IError::new(
+            GenericErrorKind::InvalidFormat,
+            format!("Unknown annotation element tag {:?}.", tag as char),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse::parse_file;
+    use super::*;
+
+    const INPUTS: &str = "tests/inputs/java/suppress";
+
+    #[test]
+    fn test_class_annotations() {
+        let class_file = parse_file(&format!("{}/SuppressedClass.class", INPUTS)).unwrap();
+        let annotations = extract_annotations(&class_file.const_pool, &class_file.attributes).unwrap();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].type_descriptor, "LSuppressTestability;");
+        let value = annotations[0].element("value").unwrap();
+        assert_eq!(value.strings(), vec!["check_no_void"]);
+    }
+
+    #[test]
+    fn test_method_annotations() {
+        let class_file = parse_file(&format!("{}/SuppressedMethod.class", INPUTS)).unwrap();
+        let values: Vec<usize> = class_file
+            .methods
+            .iter()
+            .map(|method| {
+                let annotations =
+                    extract_annotations(&class_file.const_pool, &method.attributes).unwrap();
+                annotations
+                    .iter()
+                    .filter_map(|annotation| annotation.element("value"))
+                    .map(|value| value.strings().len())
+                    .sum()
+            })
+            .collect();
+        assert_eq!(values, vec![0, 1, 0, 0, 2]);
+    }
+
+    #[test]
+    fn test_runtime_visible_annotation() {
+        let class_file = parse_file(&format!("{}/GeneratedClass.class", INPUTS)).unwrap();
+        let annotations = extract_annotations(&class_file.const_pool, &class_file.attributes).unwrap();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].type_descriptor, "LGenerated;");
+        assert!(annotations[0].elements.is_empty());
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use classfile_parser::code_attribute::Instruction;
+
+use super::code::Code;
+
+/// A straight run of instructions, entered at its first and left at its last.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicBlock {
+    /// The offset of the first instruction.
+    pub start_pc: usize,
+    /// The offset of the last instruction.
+    pub end_pc: usize,
+    /// The indices of the blocks control flows to, without exceptions.
+    pub successors: Vec<usize>,
+    /// The indices of the blocks handling the exceptions thrown in this block.
+    pub handlers: Vec<usize>,
+}
+
+/// The control-flow graph of a method body.
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph {
+    blocks: Vec<BasicBlock>,
+    /// The blocks starting a `catch` clause, `finally` blocks aside.
+    catches: BTreeSet<usize>,
+}
+
+impl ControlFlowGraph {
+    pub fn build(code: &Code) -> Self {
+        let ops = code.ops();
+
+        // A block starts at the entry, at every jump target and handler, and after every jump.
+        let mut leaders = BTreeSet::from([0]);
+        code.exception_handlers().iter().for_each(|handler| {
+            leaders.insert(handler.handler_pc as usize);
+        });
+        ops.iter().enumerate().for_each(|(index, op)| {
+            let targets = jump_targets(op.pc, &op.instruction);
+            if !targets.is_empty() || ends_flow(&op.instruction) {
+                leaders.extend(targets);
+                if let Some(next) = ops.get(index + 1) {
+                    leaders.insert(next.pc);
+                }
+            }
+        });
+        let leaders: Vec<usize> = leaders.into_iter().filter(|pc| code.op_at(*pc).is_some()).collect();
+        let block_of = |pc: usize| leaders.partition_point(|leader| *leader <= pc).saturating_sub(1);
+
+        let mut blocks: Vec<BasicBlock> = leaders
+            .iter()
+            .enumerate()
+            .filter_map(|(index, start_pc)| {
+                let next_leader = leaders.get(index + 1).copied().unwrap_or(usize::MAX);
+                let last = ops.iter().rev().find(|op| op.pc >= *start_pc && op.pc < next_leader)?;
+
+                let mut successors: Vec<usize> = jump_targets(last.pc, &last.instruction)
+                    .into_iter()
+                    .map(block_of)
+                    .collect();
+                if !ends_flow(&last.instruction) && index + 1 < leaders.len() {
+                    successors.push(index + 1);
+                }
+                successors.sort_unstable();
+                successors.dedup();
+
+                Some(BasicBlock { start_pc: *start_pc, end_pc: last.pc, successors, handlers: Vec::new() })
+            })
+            .collect();
+
+        let mut catches = BTreeSet::new();
+        code.exception_handlers().iter().for_each(|handler| {
+            let handler_block = block_of(handler.handler_pc as usize);
+            if handler.catch_type.is_some() {
+                catches.insert(handler_block);
+            }
+            blocks
+                .iter_mut()
+                .filter(|block| block.start_pc < handler.end_pc as usize && block.end_pc >= handler.start_pc as usize)
+                .for_each(|block| {
+                    if !block.handlers.contains(&handler_block) {
+                        block.handlers.push(handler_block);
+                    }
+                });
+        });
+
+        Self { blocks, catches }
+    }
+
+    pub fn blocks(&self) -> &Vec<BasicBlock> {
+        &self.blocks
+    }
+
+    /// The block holding the instruction at an offset.
+    pub fn block_at(&self, pc: usize) -> Option<&BasicBlock> {
+        self.blocks.iter().find(|block| block.start_pc <= pc && pc <= block.end_pc)
+    }
+
+    /// The number of linearly independent paths through the method: one, plus
+    /// one for every extra branch out of a block and for every `catch` clause.
+    ///
+    /// It is the number of paths a basis path testing runs; covering every
+    /// branch takes [`Self::branch_coverage_paths`], often fewer.
+    pub fn cyclomatic_complexity(&self) -> usize {
+        let branches: usize = self
+            .blocks
+            .iter()
+            .map(|block| block.successors.len().saturating_sub(1))
+            .sum();
+        1 + branches + self.catches.len()
+    }
+
+    /// The fewest paths through the method taking every branch and entering
+    /// every `catch` clause, i.e. the tests branch coverage takes. A path may
+    /// go round a loop and carry on, so loops need no path of their own.
+    pub fn branch_coverage_paths(&self) -> usize {
+        // The blocks leaving the method flow to an extra exit node.
+        let exit = self.blocks.len();
+        // Every edge, with whether a path has to take it: jumps back to a loop may be taken or not,
+        // while the normal way out of a block stays a branch when it may throw into a `catch` clause.
+        let mut edges: Vec<(usize, usize, bool)> = Vec::new();
+        self.blocks.iter().enumerate().for_each(|(index, block)| {
+            edges.extend(block.successors.iter().map(|successor| (index, *successor, *successor > index)));
+            if block.successors.is_empty() {
+                edges.push((index, exit, true));
+            }
+        });
+        // A `catch` clause is entered from the first block it protects, `finally` blocks left out.
+        self.catches.iter().for_each(|catch| {
+            if let Some(index) = self.blocks.iter().position(|block| block.handlers.contains(catch)) {
+                edges.push((index, *catch, true));
+            }
+        });
+
+        // Only the edges on a way from the entry to the exit count, e.g. not those of an endless loop.
+        let from_entry = shortest_paths(exit + 1, 0, &edges, false);
+        let to_exit = shortest_paths(exit + 1, exit, &edges, true);
+        edges.retain(|(from, to, _)| {
+            (*from == 0 || from_entry[*from].is_some()) && (*to == exit || to_exit[*to].is_some())
+        });
+        let from_entry = shortest_paths(exit + 1, 0, &edges, false);
+        let to_exit = shortest_paths(exit + 1, exit, &edges, true);
+
+        // First, a path through every edge to take, counting the paths taking each edge.
+        let mut flow = vec![0; edges.len()];
+        let mut paths = 0;
+        for (index, (from, to, required)) in edges.iter().enumerate() {
+            if !required {
+                continue;
+            }
+            flow[index] += 1;
+            let mut node = *from;
+            while let Some(edge) = from_entry[node] {
+                flow[edge] += 1;
+                node = edges[edge].0;
+            }
+            let mut node = *to;
+            while let Some(edge) = to_exit[node] {
+                flow[edge] += 1;
+                node = edges[edge].1;
+            }
+            paths += 1;
+        }
+
+        // Then, merge two paths into one for every way back from the exit to the entry,
+        // going against the edges more paths take than required, or along any edge.
+        let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); exit + 1];
+        let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); exit + 1];
+        edges.iter().enumerate().for_each(|(index, (from, to, _))| {
+            outgoing[*from].push(index);
+            incoming[*to].push(index);
+        });
+        loop {
+            // The edge each node is reached by, and whether against it.
+            let mut reached_by: Vec<Option<(usize, bool)>> = vec![None; exit + 1];
+            let mut queue = VecDeque::from([exit]);
+            while let Some(node) = queue.pop_front() {
+                let against = incoming[node]
+                    .iter()
+                    .filter(|edge| flow[**edge] > usize::from(edges[**edge].2))
+                    .map(|edge| (*edge, edges[*edge].0, true));
+                let along = outgoing[node].iter().map(|edge| (*edge, edges[*edge].1, false));
+                against.chain(along).for_each(|(edge, next, against)| {
+                    if next != exit && reached_by[next].is_none() {
+                        reached_by[next] = Some((edge, against));
+                        queue.push_back(next);
+                    }
+                });
+            }
+            if reached_by[0].is_none() {
+                break;
+            }
+
+            let mut node = 0;
+            while let Some((edge, against)) = reached_by[node] {
+                let (from, to, _) = edges[edge];
+                match against {
+                    true => {
+                        flow[edge] -= 1;
+                        node = to;
+                    }
+                    false => {
+                        flow[edge] += 1;
+                        node = from;
+                    }
+                }
+            }
+            paths -= 1;
+        }
+        paths.max(1)
+    }
+
+    /// The jumps back to an earlier block, one per loop, as the offsets of
+    /// the jump and of its target.
+    pub fn back_edges(&self) -> Vec<(usize, usize)> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(index, block)| {
+                block
+                    .successors
+                    .iter()
+                    .filter(move |successor| **successor <= index)
+                    .map(|successor| (block.end_pc, self.blocks[*successor].start_pc))
+            })
+            .collect()
+    }
+}
+
+/// For every node reached from a node, the edge it is reached by on a
+/// shortest way from that node.
+///
+/// * `backwards`: whether to follow the edges from their end to their start
+fn shortest_paths(nodes: usize, start: usize, edges: &[(usize, usize, bool)], backwards: bool) -> Vec<Option<usize>> {
+    let mut adjacent: Vec<Vec<(usize, usize)>> = vec![Vec::new(); nodes];
+    edges.iter().enumerate().for_each(|(index, (from, to, _))| match backwards {
+        true => adjacent[*to].push((index, *from)),
+        false => adjacent[*from].push((index, *to)),
+    });
+
+    let mut reached_by: Vec<Option<usize>> = vec![None; nodes];
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        adjacent[node].iter().for_each(|(edge, next)| {
+            if *next != start && reached_by[*next].is_none() {
+                reached_by[*next] = Some(*edge);
+                queue.push_back(*next);
+            }
+        });
+    }
+    reached_by
+}
+
+/// The offsets an instruction may jump to, fallthrough aside.
+fn jump_targets(pc: usize, instruction: &Instruction) -> Vec<usize> {
+    let target = |offset: i32| (pc as i64 + offset as i64) as usize;
+    match instruction {
+        Instruction::IfAcmpeq(offset)
+        | Instruction::IfAcmpne(offset)
+        | Instruction::IfIcmpeq(offset)
+        | Instruction::IfIcmpne(offset)
+        | Instruction::IfIcmplt(offset)
+        | Instruction::IfIcmpge(offset)
+        | Instruction::IfIcmpgt(offset)
+        | Instruction::IfIcmple(offset)
+        | Instruction::Ifeq(offset)
+        | Instruction::Ifne(offset)
+        | Instruction::Iflt(offset)
+        | Instruction::Ifge(offset)
+        | Instruction::Ifgt(offset)
+        | Instruction::Ifle(offset)
+        | Instruction::Ifnonnull(offset)
+        | Instruction::Ifnull(offset)
+        | Instruction::Goto(offset)
+        | Instruction::Jsr(offset) => vec![target(*offset as i32)],
+        Instruction::GotoW(offset) | Instruction::JsrW(offset) => vec![target(*offset)],
+        Instruction::Tableswitch { default, offsets, .. } => {
+            std::iter::once(default).chain(offsets).map(|offset| target(*offset)).collect()
+        }
+        Instruction::Lookupswitch { default, pairs } => std::iter::once(default)
+            .chain(pairs.iter().map(|(_, offset)| offset))
+            .map(|offset| target(*offset))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether control never falls through to the next instruction.
+fn ends_flow(instruction: &Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::Goto(_)
+            | Instruction::GotoW(_)
+            | Instruction::Tableswitch { .. }
+            | Instruction::Lookupswitch { .. }
+            | Instruction::Ireturn
+            | Instruction::Lreturn
+            | Instruction::Freturn
+            | Instruction::Dreturn
+            | Instruction::Areturn
+            | Instruction::Return
+            | Instruction::Athrow
+            | Instruction::Ret(_)
+            | Instruction::RetWide(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::parse::code_for_test;
+    use rstest::rstest;
+
+    fn graph_for_test(method: &str) -> ControlFlowGraph {
+        let code = code_for_test("tests/inputs/java/cyclomatic_complexity/Complexity.class", method, None);
+        ControlFlowGraph::build(&code.unwrap())
+    }
+
+    #[rstest]
+    #[case::straight("straight", 1)]
+    #[case::one_if("oneIf", 2)]
+    #[case::early_return("earlyReturn", 2)]
+    #[case::sequential("sequential", 3)]
+    #[case::conjunction("conjunction", 3)]
+    #[case::loop_("loop", 2)]
+    #[case::switch("switchCases", 4)]
+    #[case::try_catch("tryCatch", 2)]
+    #[case::try_finally("tryFinally", 1)]
+    fn test_cyclomatic_complexity(#[case] method: &str, #[case] expected: usize) {
+        assert_eq!(graph_for_test(method).cyclomatic_complexity(), expected);
+    }
+
+    #[rstest]
+    #[case::straight("straight", 1)]
+    #[case::one_if("oneIf", 2)]
+    #[case::sequential("sequential", 2)]
+    #[case::conjunction("conjunction", 3)]
+    #[case::loop_("loop", 1)]
+    #[case::switch("switchCases", 4)]
+    #[case::try_catch("tryCatch", 2)]
+    #[case::try_finally("tryFinally", 1)]
+    #[case::tangled("tangled", 3)]
+    fn test_branch_coverage_paths(#[case] method: &str, #[case] expected: usize) {
+        assert_eq!(graph_for_test(method).branch_coverage_paths(), expected);
+    }
+
+    #[rstest]
+    #[case::straight("straight", 0)]
+    #[case::loop_("loop", 1)]
+    #[case::switch("switchCases", 0)]
+    fn test_back_edges(#[case] method: &str, #[case] expected: usize) {
+        assert_eq!(graph_for_test(method).back_edges().len(), expected);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let graph = graph_for_test("oneIf");
+        assert_eq!(graph.blocks().len(), 3);
+        assert_eq!(graph.blocks()[0].successors, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_handlers() {
+        let graph = graph_for_test("tryCatch");
+        assert!(graph.blocks()[0].handlers.len() == 1);
+    }
+}
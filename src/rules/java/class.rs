@@ -0,0 +1,128 @@
+use classfile_parser::method_info::MethodInfo;
+use classfile_parser::{ClassAccessFlags, ClassFile};
+use std::sync::Arc;
+
+use crate::errors::generic::IError;
+
+use super::declaration::Declaration;
+use super::hierarchy::ClassIndex;
+use super::kotlin::{GeneratedMember, KotlinClass};
+use super::nesting::Nesting;
+use super::parse::{parse_bytes, parse_file};
+use super::utils::*;
+
+/// A parsed class file, along with where it was read from and the index of
+/// the classes it was analysed with.
+#[derive(Debug, Clone)]
+pub struct JavaClass {
+    class_file: ClassFile,
+    file: String,
+    name: String,
+    declaration: Declaration,
+    nesting: Nesting,
+    kotlin: Option<KotlinClass>,
+    index: Option<Arc<ClassIndex>>,
+}
+
+impl JavaClass {
+    /// Read the name, the declaration and the nesting of a parsed class file,
+    /// failing when they are malformed.
+    ///
+    /// * `class_file`: the parsed class file
+    /// * `file`: where the class file comes from, attached to the errors
+    pub fn new(class_file: ClassFile, file: String) -> Result<Self, IError> {
+        let name = extract_class_name(&class_file.const_pool, class_file.this_class)
+            .map_err(|e| e.with_path(&file))?;
+        let declaration = Declaration::parse(&class_file).map_err(|e| e.with_path(&file))?;
+        let nesting = Nesting::parse(&class_file).map_err(|e| e.with_path(&file))?;
+        let kotlin = KotlinClass::parse(&class_file);
+        Ok(Self { class_file, file, name, declaration, nesting, kotlin, index: None })
+    }
+
+    /// Parse a class file from its path.
+    ///
+    /// * `file`: the path of the classfile
+    pub fn parse(file: &String) -> Result<Self, IError> {
+        Self::new(parse_file(file)?, file.to_owned())
+    }
+
+    /// Parse a class file from its content.
+    ///
+    /// * `bytes`: the content of the classfile
+    /// * `file`: where the content comes from, used in reports
+    pub fn from_bytes(bytes: &[u8], file: String) -> Result<Self, IError> {
+        Self::new(parse_bytes(bytes)?, file)
+    }
+
+    pub fn class_file(&self) -> &ClassFile {
+        &self.class_file
+    }
+
+    pub fn file(&self) -> &String {
+        &self.file
+    }
+
+    /// The binary name of the class, e.g. `com.example.Foo`.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// What the class is declared as, e.g. a record, and for which Java release.
+    pub fn declaration(&self) -> &Declaration {
+        &self.declaration
+    }
+
+    /// Where the class is declared in the source code.
+    pub fn nesting(&self) -> &Nesting {
+        &self.nesting
+    }
+
+    /// The binary name of the top-level class this class is declared in.
+    pub fn source_class(&self) -> String {
+        self.nesting.source_class(&self.name)
+    }
+
+    /// The class as it reads in the source code, e.g. `Foo.Inner` or
+    /// ``anonymous class in `Foo.bar()` ``.
+    pub fn location(&self) -> String {
+        self.nesting.location(&self.name)
+    }
+
+    /// Whether the class was generated by the compiler, e.g. to hold the
+    /// lookup table of a `switch` on an enum.
+    pub fn is_synthetic(&self) -> bool {
+        self.class_file.access_flags.contains(ClassAccessFlags::SYNTHETIC)
+    }
+
+    /// What the Kotlin metadata of the class tells, `None` when it was not
+    /// compiled from Kotlin.
+    pub fn kotlin(&self) -> Option<&KotlinClass> {
+        self.kotlin.as_ref()
+    }
+
+    /// The kind of a method the Kotlin compiler generated, `None` for methods
+    /// written in the source code or of classes compiled from Java.
+    ///
+    /// * `name`: the name of the method
+    pub fn generated_member(&self, name: &str) -> Option<GeneratedMember> {
+        self.kotlin.as_ref()?.generated_member(name)
+    }
+
+    /// Attach the index of the classes analysed along with this one.
+    pub fn with_index(self, index: Arc<ClassIndex>) -> Self {
+        Self { index: Some(index), ..self }
+    }
+
+    pub fn index(&self) -> Option<&ClassIndex> {
+        self.index.as_deref()
+    }
+
+    /// The supertype whose method a method of this class overrides or
+    /// implements, fixing its signature, if it is known.
+    pub fn overridden(&self, method: &MethodInfo) -> Option<&String> {
+        let const_pool = &self.class_file.const_pool;
+        let name = &extract_utf8_constant(const_pool, method.name_index).ok()?.utf8_string;
+        let descriptor = &extract_utf8_constant(const_pool, method.descriptor_index).ok()?.utf8_string;
+        self.index()?.overridden(&self.name, name, descriptor)
+    }
+}
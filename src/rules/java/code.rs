@@ -0,0 +1,308 @@
+use classfile_parser::attribute_info::{code_attribute_parser, AttributeInfo};
+use classfile_parser::code_attribute::{code_parser, Instruction};
+use classfile_parser::constant_info::ConstantInfo;
+use classfile_parser::method_info::MethodInfo;
+
+use crate::errors::generic::*;
+
+use super::constant_pool::ConstantPool;
+pub use super::constant_pool::{Constant, MemberRef};
+use super::parse::nom_error;
+use super::reader::ByteReader;
+use super::utils::*;
+
+const CODE: &str = "Code";
+const LOCAL_VARIABLE_TABLE: &str = "LocalVariableTable";
+const LOCAL_VARIABLE_TYPE_TABLE: &str = "LocalVariableTypeTable";
+
+/// The constant pool entry an instruction refers to, resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reference {
+    /// The target of an `invoke*` instruction but `invokedynamic`.
+    Method(MemberRef),
+    /// The field of a `getfield`, `putfield`, `getstatic` or `putstatic`.
+    Field(MemberRef),
+    /// The class of a `new`, `anewarray`, `multianewarray`, `checkcast` or `instanceof`.
+    Class(String),
+    /// The call site of an `invokedynamic`, e.g. a lambda or a string concatenation.
+    Dynamic { name: String, descriptor: String },
+    Constant(Constant),
+}
+
+/// An instruction with its offset in the code and its resolved reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Op {
+    pub pc: usize,
+    pub instruction: Instruction,
+    pub reference: Option<Reference>,
+}
+
+/// An entry of the exception table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExceptionHandler {
+    /// The first instruction covered.
+    pub start_pc: u16,
+    /// The instruction after the last one covered.
+    pub end_pc: u16,
+    pub handler_pc: u16,
+    /// The binary name of the caught class, `None` for `finally` blocks.
+    pub catch_type: Option<String>,
+}
+
+/// A local variable, only known when the class was compiled with `-g`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalVariable {
+    pub start_pc: u16,
+    pub length: u16,
+    pub name: String,
+    pub descriptor: String,
+    /// The generic signature, e.g. `Ljava/util/List<Ljava/lang/String;>;`.
+    pub signature: Option<String>,
+    /// The slot of the variable.
+    pub index: u16,
+}
+
+/// The decoded body of a method.
+#[derive(Debug, Clone)]
+pub struct Code {
+    max_stack: u16,
+    max_locals: u16,
+    ops: Vec<Op>,
+    exception_handlers: Vec<ExceptionHandler>,
+    local_variables: Vec<LocalVariable>,
+}
+
+impl Code {
+    /// Decode the `Code` attribute of a method, `None` for abstract and native methods.
+    ///
+    /// * `constant_pool`: the constant pool of the class of the method
+    /// * `method`: the method to decode
+    pub fn decode(constant_pool: &[ConstantInfo], method: &MethodInfo) -> Result<Option<Self>, IError> {
+        let Some(attribute) = find_attribute(constant_pool, &method.attributes, CODE)? else {
+            return Ok(None);
+        };
+        let attribute = match code_attribute_parser(&attribute.info) {
+            Ok((_, attribute)) => attribute,
+            Err(e) => return Err(nom_error("Code attribute", &attribute.info, e)),
+        };
+
+        let instructions = match code_parser(&attribute.code) {
+            Ok((remaining, _)) if !remaining.is_empty() => {
+                let pc = attribute.code.len() - remaining.len();
+                return Err(IError::new(
+                    GenericErrorKind::InvalidFormat,
+                    format!("Unknown opcode {:#04x} at pc {}.", remaining[0], pc),
+                )
+                .with_offset(pc));
+            }
+            Ok((_, instructions)) => instructions,
+            Err(e) => return Err(nom_error("bytecode", &attribute.code, e)),
+        };
+
+        let ops = instructions
+            .into_iter()
+            .map(|(pc, instruction)| {
+                let reference = resolve_reference(constant_pool, &instruction)?;
+                Ok(Op { pc, instruction, reference })
+            })
+            .collect::<Result<Vec<Op>, IError>>()?;
+
+        let exception_handlers = attribute
+            .exception_table
+            .iter()
+            .map(|entry| {
+                Ok(ExceptionHandler {
+                    start_pc: entry.start_pc,
+                    end_pc: entry.end_pc,
+                    handler_pc: entry.handler_pc,
+                    catch_type: match entry.catch_type {
+                        0 => None,
+                        index => Some(extract_class_name(constant_pool, index)?),
+                    },
+                })
+            })
+            .collect::<Result<Vec<ExceptionHandler>, IError>>()?;
+
+        Ok(Some(Self {
+            max_stack: attribute.max_stack,
+            max_locals: attribute.max_locals,
+            ops,
+            exception_handlers,
+            local_variables: local_variables(constant_pool, &attribute.attributes)?,
+        }))
+    }
+
+    pub fn max_stack(&self) -> u16 {
+        self.max_stack
+    }
+
+    pub fn max_locals(&self) -> u16 {
+        self.max_locals
+    }
+
+    /// The instructions, in the order of their offsets.
+    pub fn ops(&self) -> &Vec<Op> {
+        &self.ops
+    }
+
+    /// The instruction at an offset.
+    pub fn op_at(&self, pc: usize) -> Option<&Op> {
+        self.ops
+            .binary_search_by_key(&pc, |op| op.pc)
+            .ok()
+            .map(|index| &self.ops[index])
+    }
+
+    pub fn exception_handlers(&self) -> &Vec<ExceptionHandler> {
+        &self.exception_handlers
+    }
+
+    /// The local variables, empty unless the class was compiled with `-g`.
+    pub fn local_variables(&self) -> &Vec<LocalVariable> {
+        &self.local_variables
+    }
+
+    /// The local variable held in a slot at an offset, if known.
+    pub fn local_variable(&self, index: u16, pc: usize) -> Option<&LocalVariable> {
+        self.local_variables.iter().find(|variable| {
+            let start = variable.start_pc as usize;
+            variable.index == index && (start..start + variable.length as usize).contains(&pc)
+        })
+    }
+}
+
+/// Read the local variable tables of a `Code` attribute, merging the generic
+/// signatures of the type table into the variables.
+fn local_variables(
+    constant_pool: &[ConstantInfo],
+    attributes: &[AttributeInfo],
+) -> Result<Vec<LocalVariable>, IError> {
+    let mut variables = Vec::new();
+    let mut signatures = Vec::new();
+    for attribute in attributes {
+        let name = &extract_utf8_constant(constant_pool, attribute.attribute_name_index)?.utf8_string;
+        if name != LOCAL_VARIABLE_TABLE && name != LOCAL_VARIABLE_TYPE_TABLE {
+            continue;
+        }
+
+        let mut reader = ByteReader::new(&attribute.info);
+        for _ in 0..reader.u2()? {
+            let variable = LocalVariable {
+                start_pc: reader.u2()?,
+                length: reader.u2()?,
+                name: extract_utf8_constant(constant_pool, reader.u2()?)?.utf8_string.clone(),
+                descriptor: extract_utf8_constant(constant_pool, reader.u2()?)?.utf8_string.clone(),
+                signature: None,
+                index: reader.u2()?,
+            };
+            match name.as_str() {
+                LOCAL_VARIABLE_TABLE => variables.push(variable),
+                _ => signatures.push(variable),
+            }
+        }
+    }
+
+    // The type table holds the signature where the plain table holds the descriptor.
+    signatures.into_iter().for_each(|typed| {
+        let variable = variables
+            .iter_mut()
+            .find(|variable| variable.index == typed.index && variable.start_pc == typed.start_pc);
+        if let Some(variable) = variable {
+            variable.signature = Some(typed.descriptor);
+        }
+    });
+    Ok(variables)
+}
+
+fn resolve_reference(constant_pool: &[ConstantInfo], instruction: &Instruction) -> Result<Option<Reference>, IError> {
+    let pool = ConstantPool::new(constant_pool);
+    let reference = match instruction {
+        Instruction::Invokevirtual(index)
+        | Instruction::Invokespecial(index)
+        | Instruction::Invokestatic(index)
+        | Instruction::Invokeinterface { index, .. } => Reference::Method(pool.member_ref(*index)?),
+        Instruction::Getfield(index)
+        | Instruction::Putfield(index)
+        | Instruction::Getstatic(index)
+        | Instruction::Putstatic(index) => Reference::Field(pool.member_ref(*index)?),
+        Instruction::New(index)
+        | Instruction::Anewarray(index)
+        | Instruction::Checkcast(index)
+        | Instruction::Instanceof(index)
+        | Instruction::Multianewarray { index, .. } => Reference::Class(pool.class_name(*index)?),
+        Instruction::Invokedynamic(index) => {
+            let (name, descriptor) = pool.invoke_dynamic(*index)?;
+            Reference::Dynamic { name, descriptor }
+        }
+        Instruction::Ldc(index) => Reference::Constant(pool.loadable(*index as u16)?),
+        Instruction::LdcW(index) | Instruction::Ldc2W(index) => Reference::Constant(pool.loadable(*index)?),
+        _ => return Ok(None),
+    };
+    Ok(Some(reference))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::parse;
+
+    fn code_for_test(method: &str) -> Option<Code> {
+        parse::code_for_test("tests/inputs/java/code/Decoded.class", method, None)
+    }
+
+    fn references(code: &Code) -> Vec<&Reference> {
+        code.ops().iter().filter_map(|op| op.reference.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_invocations() {
+        let code = code_for_test("compute").unwrap();
+        let references = references(&code);
+        assert!(references.contains(&&Reference::Method(MemberRef {
+            class: String::from("java.lang.Math"),
+            name: String::from("max"),
+            descriptor: String::from("(II)I"),
+        })));
+        assert!(references.contains(&&Reference::Field(MemberRef {
+            class: String::from("Decoded"),
+            name: String::from("count"),
+            descriptor: String::from("I"),
+        })));
+        assert!(references.contains(&&Reference::Class(String::from("java.lang.StringBuilder"))));
+        assert!(references.contains(&&Reference::Constant(Constant::String(String::from("total")))));
+        assert!(references.contains(&&Reference::Constant(Constant::Long(123456789012))));
+    }
+
+    #[test]
+    fn test_exception_table() {
+        let code = code_for_test("guarded").unwrap();
+        let handlers = code.exception_handlers();
+        assert_eq!(handlers.len(), 3);
+        assert_eq!(handlers[0].catch_type, Some(String::from("java.lang.IllegalStateException")));
+        assert_eq!(handlers[1].catch_type, None);
+        assert!(code.op_at(handlers[0].handler_pc as usize).is_some());
+    }
+
+    #[test]
+    fn test_local_variables() {
+        let code = code_for_test("compute").unwrap();
+        let names: Vec<&String> = code.local_variables().iter().map(|variable| &variable.name).collect();
+        assert_eq!(names, vec!["this", "value", "names", "builder"]);
+        let names = &code.local_variables()[2];
+        assert_eq!(names.signature, Some(String::from("Ljava/util/List<Ljava/lang/String;>;")));
+        assert_eq!(code.local_variable(1, 0).unwrap().name, "value");
+    }
+
+    #[test]
+    fn test_lambda() {
+        let code = code_for_test("supplier").unwrap();
+        assert!(references(&code)
+            .iter()
+            .any(|reference| matches!(reference, Reference::Dynamic { name, .. } if name == "get")));
+    }
+
+    #[test]
+    fn test_abstract_method() {
+        assert!(code_for_test("hook").is_none());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{errors::fail::Fail, types::scope::Scope, utils::pattern::GlobSet};
+
+use super::class::JavaClass;
+use super::code::{Code, MemberRef};
+use super::descriptor::MethodType;
+use super::utils::*;
+
+/// Check the body of every method in scope, abstract and native methods aside.
+///
+/// * `check`: the check of a method, given its name, descriptor and code
+pub fn check_code<F>(class: &JavaClass, scope: &Scope, check: F) -> Vec<Fail>
+where
+    F: Fn(&String, &String, &Code) -> Vec<Fail>,
+{
+    let class_file = class.class_file();
+    let const_pool = &class_file.const_pool;
+
+    class_file
+        .methods
+        .iter()
+        .filter(|method| scope.includes_method(class, method))
+        .flat_map(|method| {
+            let name = match extract_method_name(const_pool, method.name_index) {
+                Ok(name) => name,
+                Err(e) => return vec![e],
+            };
+
+            let descriptor =
+                match extract_method_descriptor(const_pool, method.descriptor_index, name) {
+                    Ok(descriptor) => descriptor,
+                    Err(e) => return vec![e],
+                };
+
+            match Code::decode(const_pool, method) {
+                Ok(Some(code)) => check(name, descriptor, &code),
+                Ok(None) => Vec::new(),
+                Err(e) => vec![Fail::from_error(name.to_owned(), e)],
+            }
+        })
+        .collect()
+}
+
+/// Whether a class belongs to the nest of a top-level class, e.g. `Foo$1` to `Foo`.
+pub fn is_in_nest(class: &str, source_class: &str) -> bool {
+    class.split('$').next() == Some(source_class)
+}
+
+/// Whether a class is a value type, matching the globs itself or through one
+/// of its known superclasses, `java.lang.Object` aside since every class extends it.
+pub fn is_value_type(class: &JavaClass, name: &str, value_types: &GlobSet) -> bool {
+    value_types.matches(name)
+        || class.index().is_some_and(|index| {
+            index
+                .superclasses(name)
+                .iter()
+                .any(|super_class| *super_class != "java.lang.Object" && value_types.matches(super_class))
+        })
+}
+
+/// A called method as it reads in Java, e.g. `com.example.Database.query(java.lang.String)`.
+pub fn method_target(target: &MemberRef) -> String {
+    let params = MethodType::parse(&target.descriptor)
+        .map(|method_type| method_type.params().iter().map(ToString::to_string).collect::<Vec<String>>().join(", "))
+        .unwrap_or_default();
+    format!("{}.{}({})", target.class, target.name, params)
+}
@@ -0,0 +1,235 @@
+use std::error::Error;
+use std::fmt;
+
+use classfile_parser::constant_info::{ConstantInfo, Utf8Constant};
+
+use crate::errors::generic::*;
+
+/// A field or method referenced by an instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberRef {
+    /// The binary name of the owning class, e.g. `java.lang.Math`.
+    pub class: String,
+    pub name: String,
+    pub descriptor: String,
+}
+
+/// A constant loaded by `ldc`, `ldc_w` or `ldc2_w`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constant {
+    Integer(i32),
+    Float(f32),
+    Long(i64),
+    Double(f64),
+    String(String),
+    /// A class literal, by its binary name.
+    Class(String),
+    /// A method type, by its descriptor.
+    MethodType(String),
+    MethodHandle,
+}
+
+/// Why a constant pool entry could not be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstantPoolError {
+    /// Index 0, which the format reserves for "no constant".
+    Zero,
+    /// An index past the last entry.
+    OutOfBounds { index: u16, len: usize },
+    /// The second slot of a `Long` or `Double` entry, which is unusable.
+    SecondSlot { index: u16 },
+    /// An entry of another type than expected.
+    Unexpected { index: u16, expected: &'static str, found: &'static str },
+}
+
+impl fmt::Display for ConstantPoolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConstantPoolError::Zero => f.write_str("Constant #0 does not exist."),
+            ConstantPoolError::OutOfBounds { index, len } => {
+                write!(f, "Constant #{} is out of bound for a constant pool of {} entries.", index, len)
+            }
+            ConstantPoolError::SecondSlot { index } => {
+                write!(f, "Constant #{} is the unusable second slot of a Long or Double.", index)
+            }
+            ConstantPoolError::Unexpected { index, expected, found } => {
+                write!(f, "Constant #{} is a {}Constant, not a {}Constant.", index, found, expected)
+            }
+        }
+    }
+}
+
+impl Error for ConstantPoolError {}
+
+impl From<ConstantPoolError> for IError {
+    fn from(error: ConstantPoolError) -> Self {
+        let kind = match error {
+            ConstantPoolError::Unexpected { .. } => GenericErrorKind::InvalidFormat,
+            _ => GenericErrorKind::NotFound,
+        };
+        IError::new(kind, error.to_string())
+    }
+}
+
+/// Typed and bounds-checked access to the constant pool of a class file, by
+/// the 1-based indices the class file uses.
+///
+/// `Long` and `Double` entries take two slots, the second one being held as
+/// an unusable entry so that indices match the class file.
+#[derive(Debug, Clone, Copy)]
+pub struct ConstantPool<'a> {
+    constants: &'a [ConstantInfo],
+}
+
+impl<'a> ConstantPool<'a> {
+    pub fn new(constants: &'a [ConstantInfo]) -> Self {
+        Self { constants }
+    }
+
+    /// The entry at an index, whatever its type.
+    pub fn get(&self, index: u16) -> Result<&'a ConstantInfo, ConstantPoolError> {
+        let slot = index.checked_sub(1).ok_or(ConstantPoolError::Zero)?;
+        match self.constants.get(slot as usize) {
+            Some(ConstantInfo::Unusable) => Err(ConstantPoolError::SecondSlot { index }),
+            Some(constant) => Ok(constant),
+            None => Err(ConstantPoolError::OutOfBounds { index, len: self.constants.len() }),
+        }
+    }
+
+    pub fn utf8_constant(&self, index: u16) -> Result<&'a Utf8Constant, ConstantPoolError> {
+        match self.get(index)? {
+            ConstantInfo::Utf8(utf8) => Ok(utf8),
+            other => Err(unexpected(index, "Utf8", other)),
+        }
+    }
+
+    pub fn utf8(&self, index: u16) -> Result<&'a String, ConstantPoolError> {
+        Ok(&self.utf8_constant(index)?.utf8_string)
+    }
+
+    /// The binary name of a class entry, e.g. `com.example.Foo`.
+    pub fn class_name(&self, index: u16) -> Result<String, ConstantPoolError> {
+        match self.get(index)? {
+            ConstantInfo::Class(class) => Ok(self.utf8(class.name_index)?.replace('/', ".")),
+            other => Err(unexpected(index, "Class", other)),
+        }
+    }
+
+    /// The name and descriptor of a name-and-type entry.
+    pub fn name_and_type(&self, index: u16) -> Result<(String, String), ConstantPoolError> {
+        match self.get(index)? {
+            ConstantInfo::NameAndType(name_and_type) => Ok((
+                self.utf8(name_and_type.name_index)?.to_owned(),
+                self.utf8(name_and_type.descriptor_index)?.to_owned(),
+            )),
+            other => Err(unexpected(index, "NameAndType", other)),
+        }
+    }
+
+    /// The field, method or interface method of a member reference entry.
+    pub fn member_ref(&self, index: u16) -> Result<MemberRef, ConstantPoolError> {
+        let (class_index, name_and_type_index) = match self.get(index)? {
+            ConstantInfo::FieldRef(field) => (field.class_index, field.name_and_type_index),
+            ConstantInfo::MethodRef(method) => (method.class_index, method.name_and_type_index),
+            ConstantInfo::InterfaceMethodRef(method) => (method.class_index, method.name_and_type_index),
+            other => return Err(unexpected(index, "MemberRef", other)),
+        };
+        let (name, descriptor) = self.name_and_type(name_and_type_index)?;
+        Ok(MemberRef { class: self.class_name(class_index)?, name, descriptor })
+    }
+
+    /// The name and descriptor of the call site of an `invokedynamic`.
+    pub fn invoke_dynamic(&self, index: u16) -> Result<(String, String), ConstantPoolError> {
+        match self.get(index)? {
+            ConstantInfo::InvokeDynamic(call_site) => self.name_and_type(call_site.name_and_type_index),
+            other => Err(unexpected(index, "InvokeDynamic", other)),
+        }
+    }
+
+    /// A constant loadable with `ldc`.
+    pub fn loadable(&self, index: u16) -> Result<Constant, ConstantPoolError> {
+        let loaded = match self.get(index)? {
+            ConstantInfo::Integer(integer) => Constant::Integer(integer.value),
+            ConstantInfo::Float(float) => Constant::Float(float.value),
+            ConstantInfo::Long(long) => Constant::Long(long.value),
+            ConstantInfo::Double(double) => Constant::Double(double.value),
+            ConstantInfo::String(string) => Constant::String(self.utf8(string.string_index)?.to_owned()),
+            ConstantInfo::Class(_) => Constant::Class(self.class_name(index)?),
+            ConstantInfo::MethodType(method_type) => Constant::MethodType(self.utf8(method_type.descriptor_index)?.to_owned()),
+            ConstantInfo::MethodHandle(_) => Constant::MethodHandle,
+            other => return Err(unexpected(index, "Loadable", other)),
+        };
+        Ok(loaded)
+    }
+}
+
+fn unexpected(index: u16, expected: &'static str, found: &ConstantInfo) -> ConstantPoolError {
+    ConstantPoolError::Unexpected { index, expected, found: type_name(found) }
+}
+
+/// The name of the type of an entry, as in the class file format.
+fn type_name(constant: &ConstantInfo) -> &'static str {
+    match constant {
+        ConstantInfo::Utf8(_) => "Utf8",
+        ConstantInfo::Integer(_) => "Integer",
+        ConstantInfo::Float(_) => "Float",
+        ConstantInfo::Long(_) => "Long",
+        ConstantInfo::Double(_) => "Double",
+        ConstantInfo::Class(_) => "Class",
+        ConstantInfo::String(_) => "String",
+        ConstantInfo::FieldRef(_) => "FieldRef",
+        ConstantInfo::MethodRef(_) => "MethodRef",
+        ConstantInfo::InterfaceMethodRef(_) => "InterfaceMethodRef",
+        ConstantInfo::NameAndType(_) => "NameAndType",
+        ConstantInfo::MethodHandle(_) => "MethodHandle",
+        ConstantInfo::MethodType(_) => "MethodType",
+        ConstantInfo::InvokeDynamic(_) => "InvokeDynamic",
+        ConstantInfo::Unusable => "Unusable",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::parse::parse_file;
+    use classfile_parser::ClassFile;
+
+    fn class_file_for_test() -> ClassFile {
+        parse_file(&String::from("tests/inputs/java/constant_pool/Wide.class")).unwrap()
+    }
+
+    #[test]
+    fn test_wide_entries() {
+        let class_file = class_file_for_test();
+        let pool = ConstantPool::new(&class_file.const_pool);
+        let index = class_file
+            .const_pool
+            .iter()
+            .position(|constant| matches!(constant, ConstantInfo::Long(_)))
+            .unwrap() as u16
+            + 1;
+        assert_eq!(pool.loadable(index).unwrap(), Constant::Long(1234567890123));
+        assert_eq!(pool.get(index + 1).unwrap_err(), ConstantPoolError::SecondSlot { index: index + 1 });
+    }
+
+    #[test]
+    fn test_invalid_index() {
+        let class_file = class_file_for_test();
+        let pool = ConstantPool::new(&class_file.const_pool);
+        let len = class_file.const_pool.len();
+        assert_eq!(pool.utf8(0).unwrap_err(), ConstantPoolError::Zero);
+        assert_eq!(pool.utf8(u16::MAX).unwrap_err(), ConstantPoolError::OutOfBounds { index: u16::MAX, len });
+    }
+
+    #[test]
+    fn test_unexpected_type() {
+        let class_file = class_file_for_test();
+        let pool = ConstantPool::new(&class_file.const_pool);
+        let index = class_file.this_class;
+        assert_eq!(pool.class_name(index).unwrap(), "Wide");
+        assert_eq!(
+            pool.utf8(index).unwrap_err(),
+            ConstantPoolError::Unexpected { index, expected: "Utf8", found: "Class" }
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use classfile_parser::code_attribute::Instruction;
+
+use crate::errors::generic::IError;
+
+use super::cfg::ControlFlowGraph;
+use super::code::{Code, MemberRef, Op, Reference};
+use super::descriptor::{JvmType, MethodType};
+use super::stack::pushed_by;
+
+/// Work a constructor does beyond assigning its fields and calling another
+/// constructor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Work {
+    /// A method called on an object that is neither the one being built nor a parameter.
+    Call(MemberRef),
+    /// A static method call.
+    StaticCall(MemberRef),
+    /// The instantiation of a class, by its binary name.
+    New(String),
+    /// A loop, jumping back to the offset of its first instruction.
+    Loop(usize),
+}
+
+/// Where a value on the operand stack comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Origin {
+    This,
+    Parameter,
+    Other,
+}
+
+/// The work done by the body of a constructor, with the offset of the
+/// instruction doing it.
+///
+/// Calls on the object being built and on the parameters are left out, as are
+/// calls whose receiver cannot be traced back within their basic block.
+///
+/// * `code`: the body of the constructor
+/// * `descriptor`: the descriptor of the constructor, e.g. `(Ljava/lang/String;)V`
+pub fn constructor_work(code: &Code, descriptor: &str) -> Result<Vec<(usize, Work)>, IError> {
+    let param_slots: usize = MethodType::parse(descriptor)?.params().iter().map(JvmType::slots).sum();
+    let ops = code.ops();
+    let graph = ControlFlowGraph::build(code);
+
+    let mut work: Vec<(usize, Work)> = ops
+        .iter()
+        .enumerate()
+        .filter_map(|(index, op)| match (&op.instruction, &op.reference) {
+            (Instruction::New(_), Some(Reference::Class(class))) => Some((op.pc, Work::New(class.to_owned()))),
+            (Instruction::Invokestatic(_), Some(Reference::Method(method))) => {
+                Some((op.pc, Work::StaticCall(method.clone())))
+            }
+            // Calls to the constructors of the superclass, of this class or of an instantiated class.
+            (Instruction::Invokespecial(_), Some(Reference::Method(method))) if method.name == "<init>" => None,
+            (
+                Instruction::Invokevirtual(_) | Instruction::Invokeinterface { .. } | Instruction::Invokespecial(_),
+                Some(Reference::Method(method)),
+            ) => match receiver(ops, index, graph.block_at(op.pc)?.start_pc, param_slots) {
+                Some(Origin::Other) => Some((op.pc, Work::Call(method.clone()))),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    work.extend(graph.back_edges().into_iter().map(|(pc, start)| (pc, Work::Loop(start))));
+    work.sort_by_key(|(pc, _)| *pc);
+    Ok(work)
+}
+
+/// Where the receiver of a call comes from, `None` when it cannot be traced
+/// back to the instruction pushing it within the block of the call.
+///
+/// * `block_start`: the offset of the first instruction of the block of the call
+fn receiver(ops: &[Op], index: usize, block_start: usize, param_slots: usize) -> Option<Origin> {
+    let Some(Reference::Method(method)) = &ops[index].reference else {
+        return None;
+    };
+    // The depth of the receiver from the top of the stack, in slots.
+    let depth: usize = MethodType::parse(&method.descriptor).ok()?.params().iter().map(JvmType::slots).sum();
+    pushed_by(ops, index, depth, block_start).map(|op| origin(&op.instruction, param_slots))
+}
+
+/// The origin of the value an instruction pushes.
+fn origin(instruction: &Instruction, param_slots: usize) -> Origin {
+    let local = match instruction {
+        Instruction::Aload0 => 0,
+        Instruction::Aload1 => 1,
+        Instruction::Aload2 => 2,
+        Instruction::Aload3 => 3,
+        Instruction::Aload(index) => *index as usize,
+        Instruction::AloadWide(index) => *index as usize,
+        _ => return Origin::Other,
+    };
+    match local {
+        0 => Origin::This,
+        local if local <= param_slots => Origin::Parameter,
+        _ => Origin::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::parse::code_for_test;
+    use rstest::rstest;
+
+    fn work_for_test(descriptor: &str) -> Vec<(usize, Work)> {
+        let code = code_for_test("tests/inputs/java/constructor_work/Constructors.class", "<init>", Some(descriptor));
+        constructor_work(&code.unwrap(), descriptor).unwrap()
+    }
+
+    fn member(class: &str, name: &str, descriptor: &str) -> MemberRef {
+        MemberRef { class: String::from(class), name: String::from(name), descriptor: String::from(descriptor) }
+    }
+
+    #[rstest]
+    #[case::assignments("(LRepository;Ljava/lang/String;)V", vec![(5, Work::New(String::from("java.util.ArrayList")))])]
+    #[case::delegation("(J)V", vec![
+        (1, Work::New(String::from("Repository"))),
+        (9, Work::StaticCall(member("java.lang.String", "valueOf", "(J)Ljava/lang/String;"))),
+    ])]
+    #[case::real_work("(Ljava/lang/String;)V", vec![
+        (5, Work::New(String::from("java.util.ArrayList"))),
+        (16, Work::New(String::from("Repository"))),
+        (28, Work::StaticCall(member("Config", "load", "(Ljava/lang/String;)Ljava/lang/String;"))),
+        (38, Work::Call(member("Repository", "connect", "()V"))),
+        (61, Work::Loop(43)),
+    ])]
+    #[case::static_helper("(LRepository;I)V", vec![
+        (5, Work::New(String::from("java.util.ArrayList"))),
+        (21, Work::StaticCall(member("Constructors", "defaultTitle", "()Ljava/lang/String;"))),
+    ])]
+    #[case::joined_receiver("(LRepository;Z)V", vec![
+        (5, Work::New(String::from("java.util.ArrayList"))),
+        (34, Work::StaticCall(member("Constructors", "defaultRepository", "()LRepository;"))),
+    ])]
+    fn test_constructor_work(#[case] descriptor: &str, #[case] expected: Vec<(usize, Work)>) {
+        assert_eq!(work_for_test(descriptor), expected);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    errors::{fail::Fail, generic::*},
+    types::{rule::*, scope::Scope},
+    utils::pattern::GlobSet,
+};
+
+use super::class::JavaClass;
+use super::common::{check_code, is_in_nest, is_value_type, method_target};
+use super::constructor::{constructor_work, Work};
+use super::new_collaborators::DEFAULT_VALUE_TYPES;
+use super::static_calls::DEFAULT_ALLOWED_STATIC_CALLS;
+
+/// Reports constructors doing more than assigning fields and calling another constructor.
+pub struct ConstructorWork;
+
+impl Rule for ConstructorWork {
+    fn id(&self) -> &'static str {
+        "constructor_work"
+    }
+
+    fn name(&self) -> &'static str {
+        "Constructors Do No Real Work"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid constructors calling objects they did not receive or static methods, instantiating classes and looping."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Every test building the object pays for the work of its constructor, which no test double can skip."
+    }
+
+    fn options(&self) -> Vec<RuleOption> {
+        vec![
+            RuleOption::strings(
+                "value_types",
+                DEFAULT_VALUE_TYPES,
+                "Classes that may be instantiated and called, or whose subclasses may: class name globs.",
+            ),
+            RuleOption::strings(
+                "allowed_static_calls",
+                DEFAULT_ALLOWED_STATIC_CALLS,
+                "Static methods that may be called: class.method globs.",
+            ),
+        ]
+    }
+
+    fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+        let value_types = GlobSet::new(&options.strings("value_types"));
+        let allowed_static_calls = GlobSet::new(&options.strings("allowed_static_calls"));
+        constructor_work_fails(class, &value_types, &allowed_static_calls, options.scope())
+    }
+}
+
+fn constructor_work_fails(
+    class: &JavaClass,
+    value_types: &GlobSet,
+    allowed_static_calls: &GlobSet,
+    scope: &Scope,
+) -> Vec<Fail> {
+    let source_class = class.source_class();
+
+    check_code(class, scope, |name, descriptor, code| {
+        if name != "<init>" {
+            return Vec::new();
+        }
+        let work = match constructor_work(code, descriptor) {
+            Ok(work) => work,
+            Err(e) => return vec![Fail::from_error(name.to_owned(), e)],
+        };
+        work.iter()
+            .filter_map(|(pc, work)| {
+                let done = match work {
+                    Work::Call(method) if !is_value_type(class, &method.class, value_types) => {
+                        format!("calls {} on an object it did not receive", method_target(method))
+                    }
+                    // Static helpers of the class, or of its nest, are implementation details.
+                    Work::StaticCall(method)
+                        if !is_in_nest(&method.class, &source_class)
+                            && !allowed_static_calls.matches(&format!("{}.{}", method.class, method.name)) =>
+                    {
+                        format!("calls the static method {}", method_target(method))
+                    }
+                    // Anonymous and nested classes are part of the implementation.
+                    Work::New(instantiated)
+                        if !is_in_nest(instantiated, &source_class)
+                            && !is_value_type(class, instantiated, value_types) =>
+                    {
+                        format!("instantiates {}", instantiated)
+                    }
+                    Work::Loop(start) => format!("loops back to pc {}", start),
+                    _ => return None,
+                };
+                Some(
+                    Fail::new(
+                        name.to_owned(),
+                        format!("This constructor {} at pc {}, work every test building the object pays for", done, pc),
+                        GenericErrorKind::RuleCheckFailed,
+                    )
+                    .with_descriptor(descriptor),
+                )
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::hierarchy::ClassIndex;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    /// The messages of the fails of the first class, the other ones only indexed.
+    fn constructor_work_for_test(files: &[&str]) -> Vec<String> {
+        let classes: Vec<JavaClass> = files
+            .iter()
+            .map(|file| JavaClass::parse(&format!("tests/inputs/java/constructor_work/{}", file)).unwrap())
+            .collect();
+        let index = Arc::new(ClassIndex::new(&classes));
+        let class = classes.into_iter().next().unwrap().with_index(index);
+        let strings = |patterns: &[&str]| patterns.iter().map(|pattern| String::from(*pattern)).collect::<Vec<String>>();
+        let value_types = GlobSet::new(&strings(DEFAULT_VALUE_TYPES));
+        let allowed_static_calls = GlobSet::new(&strings(DEFAULT_ALLOWED_STATIC_CALLS));
+        constructor_work_fails(&class, &value_types, &allowed_static_calls, &Scope::default())
+            .iter()
+            .map(|fail| fail.message().to_owned())
+            .collect()
+    }
+
+    #[rstest]
+    #[case::alone(&["Constructors.class"])]
+    #[case::indexed(&["Constructors.class", "Repository.class", "Config.class"])]
+    fn test_constructor_work(#[case] files: &[&str]) {
+        let fails = constructor_work_for_test(files);
+        assert_eq!(
+            fails,
+            vec![
+                "This constructor instantiates Repository at pc 16, work every test building the object pays for",
+                "This constructor calls the static method Config.load(java.lang.String) at pc 28, work every test building the object pays for",
+                "This constructor calls Repository.connect() on an object it did not receive at pc 38, work every test building the object pays for",
+                "This constructor loops back to pc 43 at pc 61, work every test building the object pays for",
+                "This constructor instantiates Repository at pc 1, work every test building the object pays for",
+            ]
+        );
+        assert!(constructor_work_for_test(&["Repository.class"]).is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    errors::{fail::Fail, generic::*},
+    types::{rule::*, scope::Scope},
+};
+
+use super::cfg::ControlFlowGraph;
+use super::class::JavaClass;
+use super::code::Code;
+use super::utils::*;
+
+/// Reports methods with more independent paths than the configured maximum.
+pub struct CyclomaticComplexity;
+
+impl Rule for CyclomaticComplexity {
+    fn id(&self) -> &'static str {
+        "cyclomatic_complexity"
+    }
+
+    fn name(&self) -> &'static str {
+        "Cyclomatic Complexity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Maximum cyclomatic complexity of a method, the number of independent paths through its body."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Every independent path is a test case to write before all branches are covered."
+    }
+
+    fn options(&self) -> Vec<RuleOption> {
+        vec![RuleOption::integer(
+            "max",
+            1,
+            1000,
+            10,
+            "Maximum cyclomatic complexity of a method.",
+        )]
+    }
+
+    fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+        cyclomatic_complexity(class, options.integer("max") as usize, options.scope())
+    }
+}
+
+fn cyclomatic_complexity(class: &JavaClass, max_complexity: usize, scope: &Scope) -> Vec<Fail> {
+    let class_file = class.class_file();
+    let const_pool = &class_file.const_pool;
+
+    class_file
+        .methods
+        .iter()
+        .filter(|method| scope.includes_method(class, method))
+        .filter_map(|method| {
+            let name = match extract_method_name(const_pool, method.name_index) {
+                Ok(name) => name,
+                Err(e) => return Some(e),
+            };
+
+            let descriptor =
+                match extract_method_descriptor(const_pool, method.descriptor_index, name) {
+                    Ok(descriptor) => descriptor,
+                    Err(e) => return Some(e),
+                };
+
+            let code = match Code::decode(const_pool, method) {
+                Ok(code) => code?,
+                Err(e) => return Some(Fail::from_error(name.to_owned(), e)),
+            };
+
+            let graph = ControlFlowGraph::build(&code);
+            let complexity = graph.cyclomatic_complexity();
+            if complexity > max_complexity {
+                return Some(Fail::new(
+                    name.to_owned(),
+                    format!(
+                        "This method has a cyclomatic complexity of {} (max: {}), {} test path(s) needed for branch coverage",
+                        complexity,
+                        max_complexity,
+                        graph.branch_coverage_paths()
+                    ),
+                    GenericErrorKind::RuleCheckFailed,
+                )
+                .with_descriptor(descriptor));
+            }
+            None
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn class_for_test(file: &str) -> JavaClass {
+        JavaClass::parse(&format!("tests/inputs/java/cyclomatic_complexity/{}", file)).unwrap()
+    }
+
+    #[rstest]
+    #[case::lowest(1, 8)]
+    #[case::two(2, 4)]
+    #[case::four(4, 1)]
+    #[case::tangled(9, 0)]
+    fn test_cyclomatic_complexity(#[case] max_complexity: usize, #[case] expected_errors: usize) {
+        let class = class_for_test("Complexity.class");
+        let fails = cyclomatic_complexity(&class, max_complexity, &Scope::default());
+        assert_eq!(fails.len(), expected_errors);
+    }
+
+    #[test]
+    fn test_cyclomatic_complexity_message() {
+        let class = class_for_test("Complexity.class");
+        let fails = cyclomatic_complexity(&class, 2, &Scope::default());
+        let sequential = fails.iter().find(|fail| fail.method() == "sequential").unwrap();
+        assert_eq!(
+            sequential.message(),
+            "This method has a cyclomatic complexity of 3 (max: 2), 2 test path(s) needed for branch coverage"
+        );
+    }
+}
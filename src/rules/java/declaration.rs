@@ -0,0 +1,202 @@
+use std::fmt;
+
+use classfile_parser::{ClassAccessFlags, ClassFile};
+
+use crate::errors::generic::*;
+
+use super::reader::ByteReader;
+use super::utils::*;
+
+const RECORD: &str = "Record";
+const PERMITTED_SUBCLASSES: &str = "PermittedSubclasses";
+
+/// Subtracted from the major version of a class file to get its Java release,
+/// e.g. 61 for Java 17.
+const FIRST_MAJOR_VERSION: u16 = 44;
+
+/// What a class is declared as in the source code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClassKind {
+    #[default]
+    Class,
+    Interface,
+    Annotation,
+    Enum,
+    Record,
+}
+
+impl fmt::Display for ClassKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClassKind::Class => f.write_str("class"),
+            ClassKind::Interface => f.write_str("interface"),
+            ClassKind::Annotation => f.write_str("annotation"),
+            ClassKind::Enum => f.write_str("enum"),
+            ClassKind::Record => f.write_str("record"),
+        }
+    }
+}
+
+/// A component of a record, e.g. `int x` in `record Point(int x, int y)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordComponent {
+    pub name: String,
+    pub descriptor: String,
+}
+
+/// How a class is declared, read from its access flags and its `Record` and
+/// `PermittedSubclasses` attributes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Declaration {
+    kind: ClassKind,
+    major_version: u16,
+    record_components: Vec<RecordComponent>,
+    /// The binary names of the only classes allowed to extend a sealed class.
+    permitted_subclasses: Vec<String>,
+}
+
+impl Declaration {
+    /// Read how a class is declared.
+    ///
+    /// * `class_file`: the class file of the class
+    pub fn parse(class_file: &ClassFile) -> Result<Self, IError> {
+        let const_pool = &class_file.const_pool;
+        let attributes = &class_file.attributes;
+        let flags = class_file.access_flags;
+
+        let mut record_components = Vec::new();
+        let record = find_attribute(const_pool, attributes, RECORD)?;
+        if let Some(attribute) = record {
+            let mut reader = ByteReader::new(&attribute.info);
+            for _ in 0..reader.u2()? {
+                let name = extract_utf8_constant(const_pool, reader.u2()?)?.utf8_string.clone();
+                let descriptor = extract_utf8_constant(const_pool, reader.u2()?)?.utf8_string.clone();
+                for _ in 0..reader.u2()? {
+                    reader.u2()?;
+                    let length = reader.u4()?;
+                    reader.skip(length as usize)?;
+                }
+                record_components.push(RecordComponent { name, descriptor });
+            }
+        }
+
+        let mut permitted_subclasses = Vec::new();
+        if let Some(attribute) = find_attribute(const_pool, attributes, PERMITTED_SUBCLASSES)? {
+            let mut reader = ByteReader::new(&attribute.info);
+            for _ in 0..reader.u2()? {
+                permitted_subclasses.push(extract_class_name(const_pool, reader.u2()?)?);
+            }
+        }
+
+        let kind = if flags.contains(ClassAccessFlags::ANNOTATION) {
+            ClassKind::Annotation
+        } else if flags.contains(ClassAccessFlags::INTERFACE) {
+            ClassKind::Interface
+        } else if flags.contains(ClassAccessFlags::ENUM) {
+            ClassKind::Enum
+        } else if record.is_some() {
+            ClassKind::Record
+        } else {
+            ClassKind::Class
+        };
+
+        Ok(Self { kind, major_version: class_file.major_version, record_components, permitted_subclasses })
+    }
+
+    pub fn kind(&self) -> ClassKind {
+        self.kind
+    }
+
+    /// The major version of the class file, e.g. 61 for Java 17.
+    pub fn major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    /// The Java release the class file was compiled for, e.g. 17.
+    pub fn java_version(&self) -> u16 {
+        self.major_version.saturating_sub(FIRST_MAJOR_VERSION)
+    }
+
+    pub fn record_components(&self) -> &Vec<RecordComponent> {
+        &self.record_components
+    }
+
+    /// The binary names of the permitted subclasses, empty unless the class is sealed.
+    pub fn permitted_subclasses(&self) -> &Vec<String> {
+        &self.permitted_subclasses
+    }
+
+    pub fn is_sealed(&self) -> bool {
+        !self.permitted_subclasses.is_empty()
+    }
+
+    /// Whether a method is declared by the language rather than by the
+    /// developer, who cannot change its signature: `values` and `valueOf` of
+    /// enums, and the canonical constructor, accessors, `equals`, `hashCode`
+    /// and `toString` of records.
+    ///
+    /// * `name`: the name of the method
+    /// * `descriptor`: the descriptor of the method
+    pub fn is_implicit(&self, name: &str, descriptor: &str) -> bool {
+        match self.kind {
+            ClassKind::Enum => {
+                (name == "values" && descriptor.starts_with("()["))
+                    || (name == "valueOf" && descriptor.starts_with("(Ljava/lang/String;)"))
+            }
+            ClassKind::Record => {
+                let canonical: String = self.record_components.iter().map(|component| component.descriptor.as_str()).collect();
+                (name == "<init>" && descriptor == format!("({})V", canonical))
+                    || (name == "equals" && descriptor == "(Ljava/lang/Object;)Z")
+                    || (name == "hashCode" && descriptor == "()I")
+                    || (name == "toString" && descriptor == "()Ljava/lang/String;")
+                    || self.record_components.iter().any(|component| {
+                        component.name == name && descriptor == format!("(){}", component.descriptor)
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::parse::parse_file;
+    use rstest::rstest;
+
+    fn declaration_for_test(file: &str) -> Declaration {
+        Declaration::parse(&parse_file(&format!("tests/inputs/java/modern/{}", file)).unwrap()).unwrap()
+    }
+
+    #[rstest]
+    #[case::class("Circle.class", ClassKind::Class)]
+    #[case::interface("Shape.class", ClassKind::Interface)]
+    #[case::enum_("Color.class", ClassKind::Enum)]
+    #[case::record("Point.class", ClassKind::Record)]
+    fn test_kind(#[case] file: &str, #[case] expected: ClassKind) {
+        let declaration = declaration_for_test(file);
+        assert_eq!(declaration.kind(), expected);
+        assert_eq!(declaration.major_version(), 61);
+        assert_eq!(declaration.java_version(), 17);
+    }
+
+    #[test]
+    fn test_sealed() {
+        assert_eq!(declaration_for_test("Shape.class").permitted_subclasses(), &vec!["Circle", "Square"]);
+        assert!(!declaration_for_test("Circle.class").is_sealed());
+    }
+
+    #[rstest]
+    #[case::values("Color.class", "values", "()[LColor;", true)]
+    #[case::value_of("Color.class", "valueOf", "(Ljava/lang/String;)LColor;", true)]
+    #[case::enum_method("Color.class", "paint", "()V", false)]
+    #[case::canonical("Point.class", "<init>", "(IIIII)V", true)]
+    #[case::accessor("Point.class", "x", "()I", true)]
+    #[case::equals("Point.class", "equals", "(Ljava/lang/Object;)Z", true)]
+    #[case::to_string("Point.class", "toString", "()Ljava/lang/String;", true)]
+    #[case::record_method("Point.class", "print", "()V", false)]
+    #[case::class_to_string("Circle.class", "toString", "()Ljava/lang/String;", false)]
+    fn test_is_implicit(#[case] file: &str, #[case] name: &str, #[case] descriptor: &str, #[case] expected: bool) {
+        assert_eq!(declaration_for_test(file).is_implicit(name, descriptor), expected);
+    }
+}
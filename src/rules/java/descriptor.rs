@@ -0,0 +1,242 @@
+use std::{fmt, iter::Peekable, str::Chars};
+
+use crate::errors::generic::*;
+
+/// A type as written in a JVM descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JvmType {
+    Byte,
+    Char,
+    Double,
+    Float,
+    Int,
+    Long,
+    Short,
+    Boolean,
+    /// Only valid as a return type.
+    Void,
+    /// A class or interface by its binary name, e.g. `java.lang.String`.
+    Object(String),
+    /// An array of `dimensions` dimensions of a non-array element type.
+    Array { element: Box<JvmType>, dimensions: u8 },
+}
+
+impl JvmType {
+    /// Parse a field descriptor, e.g. `[Ljava/lang/String;`.
+    pub fn parse(descriptor: &str) -> Result<Self, IError> {
+        let mut chars = descriptor.chars().peekable();
+        let parsed = parse_type(&mut chars, descriptor)?;
+        match chars.next() {
+            None if parsed != JvmType::Void => Ok(parsed),
+            _ => Err(invalid(descriptor)),
+        }
+    }
+
+    /// The class of an object type, or of the elements of an array type.
+    pub fn class_name(&self) -> Option<&String> {
+        match self {
+            JvmType::Object(name) => Some(name),
+            JvmType::Array { element, .. } => element.class_name(),
+            _ => None,
+        }
+    }
+
+    pub fn is_void(&self) -> bool {
+        *self == JvmType::Void
+    }
+
+    pub fn is_primitive(&self) -> bool {
+        !matches!(self, JvmType::Void | JvmType::Object(_) | JvmType::Array { .. })
+    }
+
+    /// Whether the type takes two local variable slots, `long` and `double`.
+    pub fn is_wide(&self) -> bool {
+        matches!(self, JvmType::Long | JvmType::Double)
+    }
+
+    /// The number of operand stack slots a value of the type takes, none for `void`.
+    pub fn slots(&self) -> usize {
+        match self {
+            JvmType::Void => 0,
+            JvmType::Long | JvmType::Double => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for JvmType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JvmType::Byte => f.write_str("byte"),
+            JvmType::Char => f.write_str("char"),
+            JvmType::Double => f.write_str("double"),
+            JvmType::Float => f.write_str("float"),
+            JvmType::Int => f.write_str("int"),
+            JvmType::Long => f.write_str("long"),
+            JvmType::Short => f.write_str("short"),
+            JvmType::Boolean => f.write_str("boolean"),
+            JvmType::Void => f.write_str("void"),
+            JvmType::Object(name) => f.write_str(name),
+            JvmType::Array { element, dimensions } => {
+                write!(f, "{}{}", element, "[]".repeat(*dimensions as usize))
+            }
+        }
+    }
+}
+
+/// The parameter and return types of a method descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodType {
+    params: Vec<JvmType>,
+    ret: JvmType,
+}
+
+impl MethodType {
+    /// Parse a method descriptor, e.g. `(I[JLjava/lang/String;)V`.
+    pub fn parse(descriptor: &str) -> Result<Self, IError> {
+        let mut chars = descriptor.chars().peekable();
+        if chars.next() != Some('(') {
+            return Err(invalid(descriptor));
+        }
+
+        let mut params = Vec::new();
+        while chars.peek() != Some(&')') {
+            match parse_type(&mut chars, descriptor)? {
+                JvmType::Void => return Err(invalid(descriptor)),
+                param => params.push(param),
+            }
+        }
+        chars.next();
+
+        let ret = parse_type(&mut chars, descriptor)?;
+        match chars.next() {
+            None => Ok(Self { params, ret }),
+            Some(_) => Err(invalid(descriptor)),
+        }
+    }
+
+    pub fn params(&self) -> &Vec<JvmType> {
+        &self.params
+    }
+
+    pub fn ret(&self) -> &JvmType {
+        &self.ret
+    }
+
+    /// Render the Java signature of a method of this type, e.g. `void run(int, java.lang.String[])`.
+    ///
+    /// * `name`: the name of the method, rendered without return type for constructors
+    pub fn signature(&self, name: &str) -> String {
+        let params = self
+            .params
+            .iter()
+            .map(JvmType::to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+        match name {
+            "<init>" | "<clinit>" => format!("{}({})", name, params),
+            _ => format!("{} {}({})", self.ret, name, params),
+        }
+    }
+}
+
+/// Parse the next type of a descriptor, `V` included.
+fn parse_type(chars: &mut Peekable<Chars>, descriptor: &str) -> Result<JvmType, IError> {
+    let parsed = match chars.next() {
+        Some('B') => JvmType::Byte,
+        Some('C') => JvmType::Char,
+        Some('D') => JvmType::Double,
+        Some('F') => JvmType::Float,
+        Some('I') => JvmType::Int,
+        Some('J') => JvmType::Long,
+        Some('S') => JvmType::Short,
+        Some('Z') => JvmType::Boolean,
+        Some('V') => JvmType::Void,
+        Some('L') => {
+            let name: String = chars.by_ref().take_while(|c| *c != ';').collect();
+            if name.is_empty() {
+                return Err(invalid(descriptor));
+            }
+            JvmType::Object(name.replace('/', "."))
+        }
+        Some('[') => {
+            let mut dimensions: u8 = 1;
+            while chars.next_if_eq(&'[').is_some() {
+                dimensions = dimensions.checked_add(1).ok_or_else(|| invalid(descriptor))?;
+            }
+            match parse_type(chars, descriptor)? {
+                JvmType::Void => return Err(invalid(descriptor)),
+                element => JvmType::Array { element: Box::new(element), dimensions },
+            }
+        }
+        _ => return Err(invalid(descriptor)),
+    };
+    Ok(parsed)
+}
+
+fn invalid(descriptor: &str) -> IError {
+    IError::new(
+        GenericErrorKind::InvalidFormat,
+        format!("Invalid descriptor `{}`.", descriptor),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::no_parameters("()V", 0)]
+    #[case::primitives("(IJZ)I", 3)]
+    #[case::objects("(Ljava/lang/String;Ljava/util/List;)V", 2)]
+    #[case::int_array("([I)V", 1)]
+    #[case::object_matrix("([[Ljava/lang/String;I)V", 2)]
+    #[case::main("([Ljava/lang/String;)V", 1)]
+    fn test_params(#[case] descriptor: &str, #[case] expected: usize) {
+        assert_eq!(MethodType::parse(descriptor).unwrap().params().len(), expected);
+    }
+
+    #[rstest]
+    #[case::void("()V", JvmType::Void)]
+    #[case::long("()J", JvmType::Long)]
+    #[case::object("()Ljava/lang/Object;", JvmType::Object(String::from("java.lang.Object")))]
+    #[case::array("()[[B", JvmType::Array { element: Box::new(JvmType::Byte), dimensions: 2 })]
+    fn test_ret(#[case] descriptor: &str, #[case] expected: JvmType) {
+        assert_eq!(MethodType::parse(descriptor).unwrap().ret(), &expected);
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::no_parenthesis("IV")]
+    #[case::no_return("(I)")]
+    #[case::unterminated("(I")]
+    #[case::void_parameter("(V)V")]
+    #[case::void_array("()[V")]
+    #[case::empty_class("(L;)V")]
+    #[case::unknown("(Q)V")]
+    #[case::trailing("()VV")]
+    fn test_invalid(#[case] descriptor: &str) {
+        assert!(MethodType::parse(descriptor).is_err());
+    }
+
+    #[rstest]
+    #[case::method("run", "(I[Ljava/lang/String;)V", "void run(int, java.lang.String[])")]
+    #[case::constructor("<init>", "(J)V", "<init>(long)")]
+    #[case::matrix("of", "()[[D", "double[][] of()")]
+    fn test_signature(#[case] name: &str, #[case] descriptor: &str, #[case] expected: &str) {
+        assert_eq!(MethodType::parse(descriptor).unwrap().signature(name), expected);
+    }
+
+    #[rstest]
+    #[case::int("I", true)]
+    #[case::array("[I", false)]
+    #[case::object("Ljava/lang/String;", false)]
+    #[case::void("V", false)]
+    fn test_field_type(#[case] descriptor: &str, #[case] primitive: bool) {
+        match JvmType::parse(descriptor) {
+            Ok(parsed) => assert_eq!(parsed.is_primitive(), primitive),
+            Err(_) => assert_eq!(descriptor, "V"),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use classfile_parser::constant_info::ConstantInfo;
+use classfile_parser::method_info::{MethodAccessFlags, MethodInfo};
+use regex::Regex;
+
+use crate::utils::pattern::glob_to_regex;
+
+use super::annotations::extract_annotations;
+
+/// The descriptor of `main(String[])`.
+const MAIN_DESCRIPTOR: &str = "([Ljava/lang/String;)V";
+
+/// Methods called by frameworks rather than by code under test.
+pub const DEFAULT_ENTRY_POINTS: &[&str] = &[
+    "run()V",
+    "doGet(*)V",
+    "doPost(*)V",
+    "doPut(*)V",
+    "doDelete(*)V",
+    "service(*)V",
+    "@org.junit.Before",
+    "@org.junit.After",
+    "@org.junit.BeforeClass",
+    "@org.junit.AfterClass",
+    "@org.junit.jupiter.api.BeforeEach",
+    "@org.junit.jupiter.api.AfterEach",
+    "@org.junit.jupiter.api.BeforeAll",
+    "@org.junit.jupiter.api.AfterAll",
+];
+
+#[derive(Debug)]
+enum EntryPoint {
+    /// A method name glob, matched with the descriptor when it holds a `(`.
+    Method { regex: Regex, with_descriptor: bool },
+    /// The descriptor of an annotation marking entry points, e.g. `Lorg/junit/Before;`.
+    Annotation(String),
+}
+
+/// Recognises the methods that are called from outside the code under test:
+/// constructors, class initialisers, `main` and configured framework methods.
+#[derive(Debug)]
+pub struct EntryPoints {
+    entry_points: Vec<EntryPoint>,
+}
+
+impl EntryPoints {
+    /// * `patterns`: method patterns, e.g. `doGet` or `run()V`, or `@`
+    ///   followed by the fully qualified name of an annotation
+    pub fn new(patterns: &[String]) -> Self {
+        let entry_points = patterns
+            .iter()
+            .map(|pattern| match pattern.strip_prefix('@') {
+                Some(annotation) => EntryPoint::Annotation(format!("L{};", annotation.replace('.', "/"))),
+                None => EntryPoint::Method {
+                    regex: glob_to_regex(pattern),
+                    with_descriptor: pattern.contains('('),
+                },
+            })
+            .collect();
+        Self { entry_points }
+    }
+
+    /// Whether a method is an entry point.
+    ///
+    /// * `const_pool`: the constant pool of the class of the method
+    /// * `method`: the method
+    /// * `name`: the name of the method
+    /// * `descriptor`: the descriptor of the method
+    pub fn includes(&self, const_pool: &[ConstantInfo], method: &MethodInfo, name: &str, descriptor: &str) -> bool {
+        if name == "<init>" || name == "<clinit>" || is_main(method.access_flags, name, descriptor) {
+            return true;
+        }
+
+        let signature = format!("{}{}", name, descriptor);
+        let annotations = extract_annotations(const_pool, &method.attributes).unwrap_or_default();
+        self.entry_points.iter().any(|entry_point| match entry_point {
+            EntryPoint::Method { regex, with_descriptor: true } => regex.is_match(&signature),
+            EntryPoint::Method { regex, with_descriptor: false } => regex.is_match(name),
+            EntryPoint::Annotation(type_descriptor) => annotations
+                .iter()
+                .any(|annotation| &annotation.type_descriptor == type_descriptor),
+        })
+    }
+}
+
+impl Default for EntryPoints {
+    fn default() -> Self {
+        let patterns: Vec<String> = DEFAULT_ENTRY_POINTS.iter().map(|pattern| String::from(*pattern)).collect();
+        Self::new(&patterns)
+    }
+}
+
+/// Whether a method is `public static void main(String[])`.
+pub fn is_main(flags: MethodAccessFlags, name: &str, descriptor: &str) -> bool {
+    name == "main"
+        && descriptor == MAIN_DESCRIPTOR
+        && flags.contains(MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::main(MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC, "main", "([Ljava/lang/String;)V", true)]
+    #[case::instance_main(MethodAccessFlags::PUBLIC, "main", "([Ljava/lang/String;)V", false)]
+    #[case::private_main(MethodAccessFlags::PRIVATE | MethodAccessFlags::STATIC, "main", "([Ljava/lang/String;)V", false)]
+    #[case::main_int(MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC, "main", "(I)V", false)]
+    #[case::maintain(MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC, "maintain", "([Ljava/lang/String;)V", false)]
+    fn test_is_main(
+        #[case] flags: MethodAccessFlags,
+        #[case] name: &str,
+        #[case] descriptor: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_main(flags, name, descriptor), expected);
+    }
+}
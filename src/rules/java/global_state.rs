@@ -0,0 +1,283 @@
+use classfile_parser::code_attribute::Instruction;
+use classfile_parser::field_info::FieldAccessFlags;
+
+use crate::{
+    errors::{fail::Fail, generic::*},
+    types::{rule::*, scope::Scope},
+    utils::pattern::GlobSet,
+};
+
+use super::cfg::ControlFlowGraph;
+use super::class::JavaClass;
+use super::code::{Code, Reference};
+use super::common::check_code;
+use super::descriptor::JvmType;
+use super::stack::pushed_by;
+use super::utils::*;
+
+/// Reports static fields holding mutable state, and the methods writing static fields.
+pub struct GlobalState;
+
+/// Types of the loggers a class may keep in a static field.
+pub const DEFAULT_LOGGER_TYPES: &[&str] = &[
+    "org.slf4j.Logger",
+    "java.util.logging.Logger",
+    "java.lang.System$Logger",
+    "org.apache.logging.log4j.Logger",
+    "org.apache.commons.logging.Log",
+    "ch.qos.logback.classic.Logger",
+];
+
+/// Types whose instances cannot change, safe to keep in a static final field.
+pub const DEFAULT_IMMUTABLE_TYPES: &[&str] = &[
+    "java.lang.String",
+    "java.lang.Boolean",
+    "java.lang.Byte",
+    "java.lang.Character",
+    "java.lang.Short",
+    "java.lang.Integer",
+    "java.lang.Long",
+    "java.lang.Float",
+    "java.lang.Double",
+    "java.lang.Class",
+    "java.math.*",
+    "java.time.**",
+    "java.util.UUID",
+    "java.util.Optional",
+    "java.util.regex.Pattern",
+];
+
+/// Static methods returning collections that cannot be modified, making the
+/// static final field they initialise a constant.
+const IMMUTABLE_FACTORIES: &[&str] = &[
+    "java.util.List.of",
+    "java.util.List.copyOf",
+    "java.util.Set.of",
+    "java.util.Set.copyOf",
+    "java.util.Map.of",
+    "java.util.Map.copyOf",
+    "java.util.Map.ofEntries",
+    "java.util.Collections.unmodifiable*",
+    "java.util.Collections.empty*",
+    "java.util.Collections.singleton*",
+    "com.google.common.collect.Immutable*.*",
+];
+
+/// The name of the field versioning serialised classes.
+const SERIAL_VERSION_UID: &str = "serialVersionUID";
+
+impl Rule for GlobalState {
+    fn id(&self) -> &'static str {
+        "global_state"
+    }
+
+    fn name(&self) -> &'static str {
+        "No Mutable Global State"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid static fields that are not final or hold mutable objects, and methods writing static fields."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Static state outlives a test and leaks into the next ones, making their results depend on their order."
+    }
+
+    fn options(&self) -> Vec<RuleOption> {
+        vec![
+            RuleOption::flag("ignore_loggers", true, "Leave out the static final fields of a logger type."),
+            RuleOption::strings(
+                "logger_types",
+                DEFAULT_LOGGER_TYPES,
+                "The logger types, class name globs.",
+            ),
+            RuleOption::flag(
+                "ignore_constants",
+                true,
+                "Leave out the static final fields of a primitive or immutable type, or holding an immutable collection.",
+            ),
+            RuleOption::strings(
+                "immutable_types",
+                DEFAULT_IMMUTABLE_TYPES,
+                "The immutable types, class name globs.",
+            ),
+            RuleOption::flag("ignore_serial_version_uid", true, "Leave out serialVersionUID."),
+        ]
+    }
+
+    fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+        let ignored = IgnoredStaticFields {
+            loggers: options.flag("ignore_loggers").then(|| GlobSet::new(&options.strings("logger_types"))),
+            constants: options.flag("ignore_constants"),
+            immutable_types: GlobSet::new(&options.strings("immutable_types")),
+            serial_version_uid: options.flag("ignore_serial_version_uid"),
+        };
+        global_state(class, &ignored, options.scope())
+    }
+}
+
+/// The static fields the `global_state` rule leaves out.
+struct IgnoredStaticFields {
+    /// The logger types, `None` when loggers are reported.
+    loggers: Option<GlobSet>,
+    constants: bool,
+    /// The immutable types, telling constants apart from the fields holding an object that can change.
+    immutable_types: GlobSet,
+    serial_version_uid: bool,
+}
+
+fn global_state(class: &JavaClass, ignored: &IgnoredStaticFields, scope: &Scope) -> Vec<Fail> {
+    let class_file = class.class_file();
+    let const_pool = &class_file.const_pool;
+    let factories: Vec<String> = IMMUTABLE_FACTORIES.iter().map(|pattern| String::from(*pattern)).collect();
+    let initialised_immutable = immutably_initialised_fields(class, &GlobSet::new(&factories));
+
+    let mut fails: Vec<Fail> = class_file
+        .fields
+        .iter()
+        .filter(|field| field.access_flags.contains(FieldAccessFlags::STATIC))
+        .filter(|field| !field.access_flags.intersects(FieldAccessFlags::SYNTHETIC | FieldAccessFlags::ENUM))
+        .filter_map(|field| {
+            let name = match extract_utf8_constant(const_pool, field.name_index) {
+                Ok(name) => &name.utf8_string,
+                Err(e) => return Some(Fail::from_error(String::new(), e)),
+            };
+            let field_type = match extract_utf8_constant(const_pool, field.descriptor_index)
+                .and_then(|descriptor| JvmType::parse(&descriptor.utf8_string))
+            {
+                Ok(field_type) => field_type,
+                Err(e) => return Some(Fail::from_error(String::new(), e)),
+            };
+
+            let is_final = field.access_flags.contains(FieldAccessFlags::FINAL);
+            let is_of = |types: Option<&GlobSet>| match (types, &field_type) {
+                (Some(types), JvmType::Object(type_name)) => types.matches(type_name),
+                _ => false,
+            };
+            let is_constant = is_final
+                && (field_type.is_primitive()
+                    || is_of(Some(&ignored.immutable_types))
+                    || initialised_immutable.contains(name));
+            let is_logger = is_final && is_of(ignored.loggers.as_ref());
+            let is_serial_version_uid = ignored.serial_version_uid && name == SERIAL_VERSION_UID;
+            if (ignored.constants && is_constant) || is_logger || is_serial_version_uid {
+                return None;
+            }
+
+            let reason = match (is_final, is_constant) {
+                (false, _) => "is not final, state shared between tests",
+                (true, false) => "holds an object that can change, state shared between tests",
+                (true, true) => "is a constant shared between tests",
+            };
+            Some(Fail::new(
+                String::new(),
+                format!("The static field {} {} {}", field_type, name, reason),
+                GenericErrorKind::RuleCheckFailed,
+            ))
+        })
+        .collect();
+
+    fails.extend(check_code(class, scope, |name, descriptor, code| {
+        if name == "<clinit>" {
+            return Vec::new();
+        }
+        code.ops()
+            .iter()
+            .filter_map(|op| match (&op.instruction, &op.reference) {
+                (Instruction::Putstatic(_), Some(Reference::Field(field))) => Some((op.pc, field)),
+                _ => None,
+            })
+            .map(|(pc, field)| {
+                Fail::new(
+                    name.to_owned(),
+                    format!(
+                        "This method writes the static field {}.{} at pc {}, state shared between tests",
+                        field.class, field.name, pc
+                    ),
+                    GenericErrorKind::RuleCheckFailed,
+                )
+                .with_descriptor(descriptor)
+            })
+            .collect()
+    }));
+    fails
+}
+
+/// The names of the static fields of a class its initialiser sets to the
+/// result of an immutable collection factory, e.g. `List.of(...)`.
+fn immutably_initialised_fields(class: &JavaClass, factories: &GlobSet) -> Vec<String> {
+    let class_file = class.class_file();
+    let const_pool = &class_file.const_pool;
+    class_file
+        .methods
+        .iter()
+        .filter(|method| {
+            extract_utf8_constant(const_pool, method.name_index).is_ok_and(|name| name.utf8_string == "<clinit>")
+        })
+        .filter_map(|method| Code::decode(const_pool, method).ok().flatten())
+        .flat_map(|code| {
+            let ops = code.ops();
+            let graph = ControlFlowGraph::build(&code);
+            ops.iter()
+                .enumerate()
+                .filter_map(|(index, op)| match (&op.instruction, &op.reference) {
+                    (Instruction::Putstatic(_), Some(Reference::Field(field))) if field.class == *class.name() => {
+                        let source = pushed_by(ops, index, 0, graph.block_at(op.pc)?.start_pc)?;
+                        match &source.reference {
+                            Some(Reference::Method(factory))
+                                if factories.matches(&format!("{}.{}", factory.class, factory.name)) =>
+                            {
+                                Some(field.name.to_owned())
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    fn global_state_for_test(file: &str, toml: &str) -> Vec<String> {
+        let class = JavaClass::parse(&format!("tests/inputs/java/global_state/{}", file)).unwrap();
+        let configured = ConfiguredRule::new(Arc::new(GlobalState), &toml.parse::<toml::Value>().unwrap()).unwrap();
+        let fails = GlobalState.check(&class, configured.options());
+        fails.iter().map(|fail| fail.to_string()).collect()
+    }
+
+    #[rstest]
+    #[case::defaults("enabled = true", 8)]
+    #[case::constants("ignore_constants = false", 14)]
+    #[case::constants_and_serial("ignore_constants = false\nignore_serial_version_uid = false", 15)]
+    #[case::loggers("ignore_loggers = false", 9)]
+    #[case::immutable_types("immutable_types = [\"java.lang.String\", \"java.util.Map\"]", 7)]
+    fn test_global_state(#[case] toml: &str, #[case] expected: usize) {
+        assert_eq!(global_state_for_test("Globals.class", toml).len(), expected);
+    }
+
+    #[test]
+    fn test_global_state_messages() {
+        let fails = global_state_for_test("Globals.class", "enabled = true");
+        assert!(fails.iter().any(|fail| fail.contains("The static field int counter is not final")));
+        assert!(fails.iter().any(|fail| fail.contains("The static field int[] TABLE holds an object that can change")));
+        assert!(fails.iter().any(|fail| fail.contains("This method writes the static field Other.value at pc 6")));
+        assert!(fails.iter().any(|fail| fail.contains("The static field java.lang.Object LOCK holds an object that can change")));
+        assert!(!fails.iter().any(|fail| fail.contains("CODES") || fail.contains("FIRST") || fail.contains("SECOND")));
+        assert_eq!(global_state_for_test("Other.class", "enabled = true").len(), 1);
+    }
+
+    #[test]
+    fn test_global_state_constant_message() {
+        let fails = global_state_for_test("Globals.class", "ignore_constants = false");
+        assert!(fails.iter().any(|fail| fail.contains("The static field int MAX is a constant shared between tests")));
+        assert!(fails.iter().any(|fail| fail.contains("The static field java.lang.String NAME is a constant")));
+        assert!(fails.iter().any(|fail| fail.contains("The static field int[] TABLE holds an object that can change")));
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use classfile_parser::method_info::MethodAccessFlags;
+
+use super::class::JavaClass;
+use super::utils::*;
+
+/// The names and methods of a class, as far as the hierarchy is concerned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedClass {
+    name: String,
+    super_class: Option<String>,
+    interfaces: Vec<String>,
+    /// The name, descriptor and access flags of each method.
+    methods: Vec<(String, String, MethodAccessFlags)>,
+}
+
+impl IndexedClass {
+    pub fn new(class: &JavaClass) -> Self {
+        let class_file = class.class_file();
+        let const_pool = &class_file.const_pool;
+
+        let super_class = match class_file.super_class {
+            0 => None,
+            index => extract_class_name(const_pool, index).ok(),
+        };
+        let interfaces = class_file
+            .interfaces
+            .iter()
+            .filter_map(|index| extract_class_name(const_pool, *index).ok())
+            .collect();
+        let methods = class_file
+            .methods
+            .iter()
+            .filter_map(|method| {
+                let name = extract_utf8_constant(const_pool, method.name_index).ok()?;
+                let descriptor = extract_utf8_constant(const_pool, method.descriptor_index).ok()?;
+                Some((name.utf8_string.clone(), descriptor.utf8_string.clone(), method.access_flags))
+            })
+            .collect();
+
+        Self { name: class.name().to_owned(), super_class, interfaces, methods }
+    }
+
+    /// The binary name of the class, e.g. `com.example.Foo`.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// The binary name of the superclass, `None` for `java.lang.Object`.
+    pub fn super_class(&self) -> Option<&String> {
+        self.super_class.as_ref()
+    }
+
+    pub fn interfaces(&self) -> &Vec<String> {
+        &self.interfaces
+    }
+
+    /// Whether the class declares a method that subclasses can override.
+    fn declares_overridable(&self, name: &str, descriptor: &str) -> bool {
+        self.methods.iter().any(|(method, method_descriptor, flags)| {
+            method == name
+                && method_descriptor == descriptor
+                && !flags.intersects(MethodAccessFlags::PRIVATE | MethodAccessFlags::STATIC)
+        })
+    }
+}
+
+/// Every class of a project, and of its classpath, by binary name.
+///
+/// Types that are neither analysed nor on the classpath, e.g. those of the
+/// JDK, are unknown: their methods are not taken into account.
+#[derive(Debug, Clone, Default)]
+pub struct ClassIndex {
+    classes: HashMap<String, IndexedClass>,
+}
+
+impl ClassIndex {
+    pub fn new(classes: &[JavaClass]) -> Self {
+        let mut index = Self::default();
+        index.add(classes);
+        index
+    }
+
+    /// Index more classes, e.g. those of the classpath, keeping the first
+    /// class indexed under a name.
+    pub fn add(&mut self, classes: &[JavaClass]) {
+        classes.iter().for_each(|class| {
+            self.classes
+                .entry(class.name().to_owned())
+                .or_insert_with(|| IndexedClass::new(class));
+        });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&IndexedClass> {
+        self.classes.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// The superclasses of a class, from its parent up, as far as they are known.
+    pub fn superclasses(&self, name: &str) -> Vec<&String> {
+        let mut superclasses = Vec::new();
+        let mut current = self.get(name).and_then(IndexedClass::super_class);
+        while let Some(super_class) = current {
+            if superclasses.contains(&super_class) {
+                break;
+            }
+            superclasses.push(super_class);
+            current = self.get(super_class).and_then(IndexedClass::super_class);
+        }
+        superclasses
+    }
+
+    /// Every superclass and interface a class inherits from, as far as they are known.
+    pub fn supertypes(&self, name: &str) -> Vec<&String> {
+        let mut supertypes: Vec<&String> = Vec::new();
+        let mut pending: Vec<&String> = match self.get(name) {
+            Some(class) => class.super_class().into_iter().chain(class.interfaces()).collect(),
+            None => Vec::new(),
+        };
+        while let Some(supertype) = pending.pop() {
+            if supertypes.contains(&supertype) {
+                continue;
+            }
+            supertypes.push(supertype);
+            if let Some(class) = self.get(supertype) {
+                pending.extend(class.super_class().into_iter().chain(class.interfaces()));
+            }
+        }
+        supertypes
+    }
+
+    /// The supertype declaring the method a method of a class overrides or
+    /// implements, if it is known.
+    ///
+    /// * `class`: the binary name of the class declaring the method
+    /// * `name`: the name of the method
+    /// * `descriptor`: the descriptor of the method
+    pub fn overridden(&self, class: &str, name: &str, descriptor: &str) -> Option<&String> {
+        if name.starts_with('<') {
+            return None;
+        }
+        self.supertypes(class).into_iter().find(|supertype| {
+            self.get(supertype)
+                .is_some_and(|indexed| indexed.declares_overridable(name, descriptor))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const HIERARCHY: &str = "tests/inputs/java/hierarchy";
+
+    fn index_for_test(files: &[&str]) -> ClassIndex {
+        let classes: Vec<JavaClass> = files
+            .iter()
+            .map(|file| JavaClass::parse(&format!("{}/{}", HIERARCHY, file)).unwrap())
+            .collect();
+        ClassIndex::new(&classes)
+    }
+
+    #[test]
+    fn test_supertypes() {
+        let index = index_for_test(&["Impl.class", "Base.class", "Listener.class"]);
+        assert_eq!(index.superclasses("Impl"), vec!["Base", "java.lang.Object"]);
+        let mut supertypes = index.supertypes("Impl");
+        supertypes.sort();
+        assert_eq!(supertypes, vec!["Base", "Listener", "java.lang.Object"]);
+    }
+
+    #[rstest]
+    #[case::abstract_method("handle", "(IIIII)V", Some("Base"))]
+    #[case::overridden("hook", "()V", Some("Base"))]
+    #[case::implemented("onEvent", "()V", Some("Listener"))]
+    #[case::own("own", "()V", None)]
+    #[case::overload("hook", "(I)V", None)]
+    #[case::constructor("<init>", "()V", None)]
+    fn test_overridden(#[case] name: &str, #[case] descriptor: &str, #[case] expected: Option<&str>) {
+        let index = index_for_test(&["Impl.class", "Base.class", "Listener.class"]);
+        assert_eq!(index.overridden("Impl", name, descriptor).map(String::as_str), expected);
+    }
+
+    #[test]
+    fn test_unknown_supertypes() {
+        let index = index_for_test(&["Impl.class"]);
+        assert!(index.overridden("Impl", "onEvent", "()V").is_none());
+        assert_eq!(index.superclasses("Impl"), vec!["Base"]);
+    }
+}
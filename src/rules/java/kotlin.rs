@@ -0,0 +1,173 @@
+use std::fmt;
+
+use classfile_parser::ClassFile;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::annotations::extract_annotations;
+use super::utils::*;
+
+/// The descriptor of the annotation the Kotlin compiler puts on every class.
+const KOTLIN_METADATA: &str = "Lkotlin/Metadata;";
+
+/// A method the Kotlin compiler generates, rather than one written in the source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeneratedMember {
+    /// `componentN` of a data class, used by destructuring declarations.
+    Component,
+    /// `copy` of a data class.
+    Copy,
+    /// The `$default` variant of a function with default arguments.
+    DefaultArguments,
+    /// The body of a lambda, desugared by R8 or by the compiler.
+    Lambda,
+    /// An `access$` method letting a companion or nested class reach a private member.
+    SyntheticAccessor,
+    /// The getter or setter of a property.
+    PropertyAccessor { property: String, setter: bool },
+}
+
+impl fmt::Display for GeneratedMember {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeneratedMember::Component => f.write_str("data class component"),
+            GeneratedMember::Copy => f.write_str("data class copy"),
+            GeneratedMember::DefaultArguments => f.write_str("default arguments"),
+            GeneratedMember::Lambda => f.write_str("lambda"),
+            GeneratedMember::SyntheticAccessor => f.write_str("synthetic accessor"),
+            GeneratedMember::PropertyAccessor { property, setter: false } => write!(f, "property {} (getter)", property),
+            GeneratedMember::PropertyAccessor { property, setter: true } => write!(f, "property {} (setter)", property),
+        }
+    }
+}
+
+/// What the linter needs to know about a class compiled from Kotlin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KotlinClass {
+    /// The names of the fields, which back the properties.
+    fields: Vec<String>,
+    /// Whether the class has the `componentN` and `copy` methods of a data class.
+    data_class: bool,
+}
+
+impl KotlinClass {
+    /// Read a class compiled from Kotlin, `None` when it lacks `@kotlin.Metadata`.
+    ///
+    /// * `class_file`: the class file of the class
+    pub fn parse(class_file: &ClassFile) -> Option<Self> {
+        let const_pool = &class_file.const_pool;
+        extract_annotations(const_pool, &class_file.attributes)
+            .ok()?
+            .iter()
+            .find(|annotation| annotation.type_descriptor == KOTLIN_METADATA)?;
+
+        let fields = class_file
+            .fields
+            .iter()
+            .filter_map(|field| extract_utf8_constant(const_pool, field.name_index).ok())
+            .map(|name| name.utf8_string.clone())
+            .collect();
+        let methods: Vec<&String> = class_file
+            .methods
+            .iter()
+            .filter_map(|method| extract_utf8_constant(const_pool, method.name_index).ok())
+            .map(|name| &name.utf8_string)
+            .collect();
+        let data_class = methods.iter().any(|name| *name == "component1") && methods.iter().any(|name| *name == "copy");
+
+        Some(Self { fields, data_class })
+    }
+
+    /// Classify a method generated by the compiler, `None` for methods
+    /// written in the source code.
+    ///
+    /// * `name`: the name of the method
+    pub fn generated_member(&self, name: &str) -> Option<GeneratedMember> {
+        lazy_static! {
+            static ref COMPONENT_REGEX: Regex = Regex::new(r"^component[1-9][0-9]*$").unwrap();
+            static ref ACCESSOR_REGEX: Regex = Regex::new(r"^(get|set|is)([A-Z].*)$").unwrap();
+        }
+
+        if name.starts_with("access$") {
+            return Some(GeneratedMember::SyntheticAccessor);
+        }
+        if name.contains("$r8$lambda") || name.starts_with("lambda$") || name.contains("$lambda$") {
+            return Some(GeneratedMember::Lambda);
+        }
+        if name.ends_with("$default") {
+            return Some(GeneratedMember::DefaultArguments);
+        }
+        if self.data_class && COMPONENT_REGEX.is_match(name) {
+            return Some(GeneratedMember::Component);
+        }
+        if self.data_class && name == "copy" {
+            return Some(GeneratedMember::Copy);
+        }
+
+        let captures = ACCESSOR_REGEX.captures(name)?;
+        let (prefix, rest) = (&captures[1], &captures[2]);
+        // Properties named `isFoo` keep their name for the getter and the backing field.
+        let property = match prefix {
+            "is" => name.to_owned(),
+            _ => decapitalize(rest),
+        };
+        let property = match self.fields.contains(&property) {
+            true => property,
+            false if prefix == "set" && self.fields.contains(&format!("is{}", rest)) => format!("is{}", rest),
+            false => return None,
+        };
+        Some(GeneratedMember::PropertyAccessor { property, setter: prefix == "set" })
+    }
+}
+
+/// Lower the first letter of an accessor suffix, keeping acronyms such as
+/// `URL` in `getURL` whole.
+fn decapitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(_) if chars.next().is_some_and(char::is_uppercase) => name.to_owned(),
+        Some(first) => first.to_lowercase().chain(name.chars().skip(1)).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::parse::parse_file;
+    use rstest::rstest;
+
+    fn kotlin_for_test(file: &str) -> Option<KotlinClass> {
+        KotlinClass::parse(&parse_file(&format!("tests/inputs/java/kotlin/{}", file)).unwrap())
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(kotlin_for_test("Point.class").is_some());
+        assert!(kotlin_for_test("Plain.class").is_none());
+    }
+
+    #[rstest]
+    #[case::getter("getX", Some("property x (getter)"))]
+    #[case::setter("setY", Some("property y (setter)"))]
+    #[case::boolean_getter("isActive", Some("property isActive (getter)"))]
+    #[case::component("component2", Some("data class component"))]
+    #[case::copy("copy", Some("data class copy"))]
+    #[case::default_arguments("copy$default", Some("default arguments"))]
+    #[case::synthetic_accessor("access$getX", Some("synthetic accessor"))]
+    #[case::r8_lambda("$r8$lambda$move", Some("lambda"))]
+    #[case::computed("getTotal", None)]
+    #[case::own("move", None)]
+    fn test_generated_member(#[case] name: &str, #[case] expected: Option<&str>) {
+        let kotlin = kotlin_for_test("Point.class").unwrap();
+        assert_eq!(kotlin.generated_member(name).map(|member| member.to_string()).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case::lower("Foo", "foo")]
+    #[case::acronym("URL", "URL")]
+    #[case::single("X", "x")]
+    fn test_decapitalize(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(decapitalize(name), expected);
+    }
+}
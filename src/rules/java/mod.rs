@@ -1,6 +1,55 @@
 pub mod rules;
 
+pub mod common;
+
+pub mod static_calls;
+
+pub mod new_collaborators;
+
+pub mod singletons;
+
+pub mod global_state;
+
+pub mod constructor_work;
+
+pub mod cyclomatic_complexity;
+
+pub mod package_cycles;
+
 pub mod parse;
 
-mod utils;
+pub mod project;
+
+pub mod class;
+
+pub mod declaration;
+
+pub mod cfg;
+
+pub mod code;
+
+pub mod constant_pool;
+
+pub mod descriptor;
+
+pub mod entry_points;
+
+pub mod hierarchy;
+
+pub mod nesting;
+
+pub mod kotlin;
+
+pub mod singleton;
+
+pub mod constructor;
+
+pub mod stack;
+
+pub mod annotations;
+
+pub mod suppress;
+
+mod reader;
 
+pub mod utils;
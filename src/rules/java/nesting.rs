@@ -0,0 +1,207 @@
+use classfile_parser::ClassFile;
+
+use crate::errors::generic::*;
+
+use super::constant_pool::ConstantPool;
+use super::descriptor::MethodType;
+use super::reader::ByteReader;
+use super::utils::*;
+
+const INNER_CLASSES: &str = "InnerClasses";
+const ENCLOSING_METHOD: &str = "EnclosingMethod";
+const NEST_HOST: &str = "NestHost";
+
+/// How a class is declared in the source code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NestingKind {
+    TopLevel,
+    /// Declared in the body of another class, e.g. `Foo$Inner`.
+    Member,
+    /// Declared with a name in a method, e.g. `Foo$1Local`.
+    Local,
+    /// Declared without a name, e.g. `Foo$1`.
+    Anonymous,
+}
+
+/// The method a local or anonymous class is declared in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnclosingMethod {
+    pub name: String,
+    pub descriptor: String,
+}
+
+/// Where a class is declared, read from its `InnerClasses`, `EnclosingMethod`
+/// and `NestHost` attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nesting {
+    kind: NestingKind,
+    /// The binary name of the class the class is declared in.
+    outer: Option<String>,
+    /// The name of the class in the source code, `None` for anonymous classes.
+    simple_name: Option<String>,
+    method: Option<EnclosingMethod>,
+    /// The binary name of the top-level class of the nest, from Java 11 on.
+    nest_host: Option<String>,
+}
+
+impl Default for Nesting {
+    fn default() -> Self {
+        Self { kind: NestingKind::TopLevel, outer: None, simple_name: None, method: None, nest_host: None }
+    }
+}
+
+impl Nesting {
+    /// Read where a class is declared.
+    ///
+    /// * `class_file`: the class file of the class
+    pub fn parse(class_file: &ClassFile) -> Result<Self, IError> {
+        let const_pool = &class_file.const_pool;
+        let attributes = &class_file.attributes;
+        let mut nesting = Self::default();
+
+        if let Some(attribute) = find_attribute(const_pool, attributes, NEST_HOST)? {
+            let host = ByteReader::new(&attribute.info).u2()?;
+            nesting.nest_host = Some(extract_class_name(const_pool, host)?);
+        }
+
+        if let Some(attribute) = find_attribute(const_pool, attributes, ENCLOSING_METHOD)? {
+            let mut reader = ByteReader::new(&attribute.info);
+            nesting.outer = Some(extract_class_name(const_pool, reader.u2()?)?);
+            nesting.method = match reader.u2()? {
+                0 => None,
+                index => {
+                    let (name, descriptor) = ConstantPool::new(const_pool).name_and_type(index)?;
+                    Some(EnclosingMethod { name, descriptor })
+                }
+            };
+        }
+
+        if let Some(attribute) = find_attribute(const_pool, attributes, INNER_CLASSES)? {
+            let mut reader = ByteReader::new(&attribute.info);
+            for _ in 0..reader.u2()? {
+                let (inner, outer, name) = (reader.u2()?, reader.u2()?, reader.u2()?);
+                reader.u2()?;
+                if inner != class_file.this_class {
+                    continue;
+                }
+
+                nesting.simple_name = match name {
+                    0 => None,
+                    name => Some(extract_utf8_constant(const_pool, name)?.utf8_string.clone()),
+                };
+                nesting.kind = match (outer, &nesting.simple_name) {
+                    (0, None) => NestingKind::Anonymous,
+                    (0, Some(_)) => NestingKind::Local,
+                    (outer, _) => {
+                        nesting.outer = Some(extract_class_name(const_pool, outer)?);
+                        NestingKind::Member
+                    }
+                };
+            }
+        }
+
+        Ok(nesting)
+    }
+
+    pub fn kind(&self) -> NestingKind {
+        self.kind
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        self.kind == NestingKind::Anonymous
+    }
+
+    #[cfg(test)]
+    pub fn method(&self) -> Option<&EnclosingMethod> {
+        self.method.as_ref()
+    }
+
+    /// The binary name of the top-level class a class is declared in, itself
+    /// for top-level classes.
+    ///
+    /// * `name`: the binary name of the class
+    pub fn source_class(&self, name: &str) -> String {
+        if let Some(host) = &self.nest_host {
+            return host.to_owned();
+        }
+        match self.kind {
+            NestingKind::TopLevel => name.to_owned(),
+            _ => name.split('$').next().unwrap_or(name).to_owned(),
+        }
+    }
+
+    /// The class as it reads in the source code, e.g. `Foo.Inner` or
+    /// ``anonymous class in `Foo.bar()` ``.
+    ///
+    /// * `name`: the binary name of the class
+    pub fn location(&self, name: &str) -> String {
+        let outer = self.outer.as_deref().map(source_name);
+        match (self.kind, outer) {
+            (NestingKind::Member, Some(outer)) => {
+                format!("{}.{}", outer, self.simple_name.as_deref().unwrap_or_default())
+            }
+            (NestingKind::Local, Some(outer)) => format!(
+                "local class {} in `{}`",
+                self.simple_name.as_deref().unwrap_or_default(),
+                self.enclosing(&outer)
+            ),
+            (NestingKind::Anonymous, Some(outer)) => format!("anonymous class in `{}`", self.enclosing(&outer)),
+            _ => source_name(name),
+        }
+    }
+
+    /// The method, or class, a local or anonymous class is declared in, e.g. `Foo.bar(int)`.
+    fn enclosing(&self, outer: &str) -> String {
+        let Some(method) = &self.method else {
+            return outer.to_owned();
+        };
+        let params = MethodType::parse(&method.descriptor)
+            .map(|method_type| {
+                method_type.params().iter().map(ToString::to_string).collect::<Vec<String>>().join(", ")
+            })
+            .unwrap_or_default();
+        match method.name.as_str() {
+            "<init>" => format!("{}({})", outer, params),
+            "<clinit>" => outer.to_owned(),
+            name => format!("{}.{}({})", outer, name, params),
+        }
+    }
+}
+
+/// A binary class name as written in the source code, e.g. `Foo.Inner` for `Foo$Inner`.
+fn source_name(name: &str) -> String {
+    name.replace('$', ".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::parse::parse_file;
+    use rstest::rstest;
+
+    fn nesting_for_test(file: &str) -> Nesting {
+        let class_file = parse_file(&format!("tests/inputs/java/nesting/{}", file)).unwrap();
+        Nesting::parse(&class_file).unwrap()
+    }
+
+    #[rstest]
+    #[case::top_level("Outer.class", NestingKind::TopLevel, "Outer")]
+    #[case::member("Outer$Inner.class", NestingKind::Member, "Outer.Inner")]
+    #[case::nested_member("Outer$Inner$Deep.class", NestingKind::Member, "Outer.Inner.Deep")]
+    #[case::anonymous_in_method("Outer$2.class", NestingKind::Anonymous, "anonymous class in `Outer.bar(int)`")]
+    #[case::anonymous_in_field("Outer$1.class", NestingKind::Anonymous, "anonymous class in `Outer`")]
+    #[case::local("Outer$1Local.class", NestingKind::Local, "local class Local in `Outer.local()`")]
+    fn test_location(#[case] file: &str, #[case] kind: NestingKind, #[case] expected: &str) {
+        let nesting = nesting_for_test(file);
+        let name = file.trim_end_matches(".class");
+        assert_eq!(nesting.kind(), kind);
+        assert_eq!(nesting.location(name), expected);
+        assert_eq!(nesting.source_class(name), "Outer");
+    }
+
+    #[test]
+    fn test_enclosing_method() {
+        let nesting = nesting_for_test("Outer$2.class");
+        assert_eq!(nesting.method().map(|method| method.descriptor.as_str()), Some("(I)Ljava/util/function/Supplier;"));
+    }
+}
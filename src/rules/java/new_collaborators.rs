@@ -0,0 +1,174 @@
+use classfile_parser::code_attribute::Instruction;
+
+use crate::{
+    errors::{fail::Fail, generic::*},
+    types::{rule::*, scope::Scope},
+    utils::pattern::GlobSet,
+};
+
+use super::class::JavaClass;
+use super::code::Reference;
+use super::common::{check_code, is_in_nest, is_value_type};
+
+/// Reports objects instantiated with `new` in methods, rather than injected.
+pub struct NewCollaborators;
+
+/// Methods whose purpose is to create objects.
+pub const DEFAULT_FACTORY_METHODS: &[&str] = &["create*", "new*", "build*", "make*", "of", "from*", "valueOf", "copyOf"];
+
+/// Types of plain values, safe to instantiate anywhere, also matched against
+/// the known superclasses of an instantiated class.
+pub const DEFAULT_VALUE_TYPES: &[&str] = &[
+    "java.lang.Object",
+    "java.lang.String",
+    "java.lang.StringBuilder",
+    "java.lang.StringBuffer",
+    "java.lang.Boolean",
+    "java.lang.Byte",
+    "java.lang.Character",
+    "java.lang.Short",
+    "java.lang.Integer",
+    "java.lang.Long",
+    "java.lang.Float",
+    "java.lang.Double",
+    "java.lang.Record",
+    "java.lang.Throwable",
+    "**Exception",
+    "**Error",
+    "java.util.**List",
+    "java.util.**Map",
+    "java.util.**Set",
+    "java.util.**Queue",
+    "java.util.**Deque",
+    "java.util.StringJoiner",
+    "java.util.concurrent.atomic.*",
+    "java.math.*",
+    "java.time.**",
+];
+
+impl Rule for NewCollaborators {
+    fn id(&self) -> &'static str {
+        "new_collaborators"
+    }
+
+    fn name(&self) -> &'static str {
+        "No Hard-Coded Collaborators"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid instantiating collaborators outside constructors and factory methods, values aside."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "An object created inside a method cannot be replaced by a test double, it has to be injected instead."
+    }
+
+    fn options(&self) -> Vec<RuleOption> {
+        vec![
+            RuleOption::strings(
+                "value_types",
+                DEFAULT_VALUE_TYPES,
+                "Classes that may be instantiated anywhere, or whose subclasses may: class name globs, e.g. com.example.model.**.",
+            ),
+            RuleOption::strings(
+                "factory_methods",
+                DEFAULT_FACTORY_METHODS,
+                "Methods that may instantiate any class: method name globs.",
+            ),
+        ]
+    }
+
+    fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+        let factory_methods = GlobSet::new(&options.strings("factory_methods"));
+        let value_types = GlobSet::new(&options.strings("value_types"));
+        new_collaborators(class, &factory_methods, &value_types, options.scope())
+    }
+}
+
+fn new_collaborators(class: &JavaClass, factory_methods: &GlobSet, value_types: &GlobSet, scope: &Scope) -> Vec<Fail> {
+    let source_class = class.source_class();
+    let is_value = |name: &String| is_value_type(class, name, value_types);
+
+    check_code(class, scope, |name, descriptor, code| {
+        if name == "<init>" || name == "<clinit>" || factory_methods.matches(name) {
+            return Vec::new();
+        }
+        code.ops()
+            .iter()
+            .filter_map(|op| match (&op.instruction, &op.reference) {
+                (Instruction::New(_), Some(Reference::Class(instantiated))) => Some((op.pc, instantiated)),
+                _ => None,
+            })
+            // Anonymous and nested classes are part of the implementation.
+            .filter(|(_, instantiated)| !is_in_nest(instantiated, &source_class) && !is_value(instantiated))
+            .map(|(pc, instantiated)| {
+                Fail::new(
+                    name.to_owned(),
+                    format!(
+                        "This method instantiates {} at pc {}, a collaborator tests cannot replace",
+                        instantiated, pc
+                    ),
+                    GenericErrorKind::RuleCheckFailed,
+                )
+                .with_descriptor(descriptor)
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::hierarchy::ClassIndex;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    fn class_for_test(file: &str) -> JavaClass {
+        JavaClass::parse(&format!("tests/inputs/java/new_collaborators/{}", file)).unwrap()
+    }
+
+    fn new_collaborators_for_test(class: &JavaClass, extra_value_types: &[&str]) -> Vec<String> {
+        let strings = |patterns: &[&str]| patterns.iter().map(|pattern| String::from(*pattern)).collect::<Vec<String>>();
+        let mut value_types = strings(DEFAULT_VALUE_TYPES);
+        value_types.extend(strings(extra_value_types));
+        let factory_methods = GlobSet::new(&strings(DEFAULT_FACTORY_METHODS));
+        new_collaborators(class, &factory_methods, &GlobSet::new(&value_types), &Scope::default())
+            .iter()
+            .map(|fail| fail.method().to_owned())
+            .collect()
+    }
+
+    #[rstest]
+    #[case::defaults(&[], vec!["pay", "total", "receipt"])]
+    #[case::value_package(&["Receipt"], vec!["pay", "total"])]
+    fn test_new_collaborators(#[case] value_types: &[&str], #[case] expected: Vec<&str>) {
+        let class = class_for_test("Orders.class");
+        assert_eq!(new_collaborators_for_test(&class, value_types), expected);
+    }
+
+    // The record is only recognised as a value once its superclass is known,
+    // while the collaborators extending java.lang.Object are still reported.
+    #[rstest]
+    #[case::record(&["Orders.class", "Money.class", "OrderException.class"])]
+    #[case::collaborators(&["Orders.class", "Money.class", "OrderException.class", "PaymentGateway.class", "Receipt.class"])]
+    fn test_new_collaborators_indexed(#[case] files: &[&str]) {
+        let classes: Vec<JavaClass> = files.iter().map(|file| class_for_test(file)).collect();
+        let index = Arc::new(ClassIndex::new(&classes));
+        let class = classes.into_iter().next().unwrap().with_index(index);
+        let fails = new_collaborators_for_test(&class, &[]);
+        assert_eq!(fails, vec!["pay", "receipt"]);
+    }
+
+    #[test]
+    fn test_new_collaborators_message() {
+        let class = class_for_test("Orders.class");
+        let strings = |patterns: &[&str]| patterns.iter().map(|pattern| String::from(*pattern)).collect::<Vec<String>>();
+        let fails = new_collaborators(
+            &class,
+            &GlobSet::new(&strings(DEFAULT_FACTORY_METHODS)),
+            &GlobSet::new(&strings(DEFAULT_VALUE_TYPES)),
+            &Scope::default(),
+        );
+        assert_eq!(fails[0].message(), "This method instantiates PaymentGateway at pc 0, a collaborator tests cannot replace");
+    }
+}
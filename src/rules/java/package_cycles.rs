@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use classfile_parser::constant_info::ConstantInfo;
+
+use crate::{
+    errors::{fail::Fail, generic::*},
+    types::rule::*,
+};
+
+use super::class::JavaClass;
+use super::descriptor::{JvmType, MethodType};
+use super::project::{package_of, Project, ProjectFail, Target};
+use super::utils::*;
+
+/// Reports packages depending on each other, directly or through other packages.
+pub struct PackageCycles;
+
+impl Rule for PackageCycles {
+    fn id(&self) -> &'static str {
+        "package_cycles"
+    }
+
+    fn name(&self) -> &'static str {
+        "No Package Cycles"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid dependency cycles between the analysed packages."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Packages in a cycle cannot be tested, nor replaced by test doubles, one without the others."
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Project
+    }
+
+    fn check_project(&self, project: &Project, _options: &RuleOptions) -> Vec<ProjectFail> {
+        package_cycles(project)
+    }
+}
+
+fn package_cycles(project: &Project) -> Vec<ProjectFail> {
+    let packages = project.packages();
+    let mut dependencies: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    project.classes().iter().for_each(|class| {
+        let package = package_of(class.name());
+        let referenced = referenced_classes(class)
+            .into_iter()
+            .map(|name| package_of(&name).to_owned())
+            .filter(|dependency| dependency != package && packages.contains(dependency.as_str()));
+        dependencies.entry(package).or_default().extend(referenced);
+    });
+
+    let reachable: BTreeMap<&str, BTreeSet<&str>> = packages
+        .iter()
+        .map(|package| (*package, reachable_packages(&dependencies, package)))
+        .collect();
+
+    packages
+        .iter()
+        .filter_map(|package| {
+            let cycle: Vec<&str> = reachable[package]
+                .iter()
+                .filter(|other| *other != package && reachable[*other].contains(package))
+                .copied()
+                .collect();
+            if cycle.is_empty() {
+                return None;
+            }
+            let cycle: Vec<String> = cycle.iter().map(|other| Target::Package(other.to_string()).to_string()).collect();
+            Some(ProjectFail {
+                target: Target::Package(package.to_string()),
+                fail: Fail::new(
+                    String::new(),
+                    format!(
+                        "{} is in a dependency cycle with {}",
+                        Target::Package(package.to_string()),
+                        cycle.join(", ")
+                    ),
+                    GenericErrorKind::RuleCheckFailed,
+                ),
+            })
+        })
+        .collect()
+}
+
+/// The packages a package depends on, directly or not, itself included when it is in a cycle.
+fn reachable_packages<'a>(dependencies: &'a BTreeMap<&str, BTreeSet<String>>, package: &str) -> BTreeSet<&'a str> {
+    let mut reachable = BTreeSet::new();
+    let mut pending: Vec<&str> = vec![package];
+    while let Some(current) = pending.pop() {
+        dependencies
+            .get(current)
+            .into_iter()
+            .flatten()
+            .for_each(|dependency| {
+                if reachable.insert(dependency.as_str()) {
+                    pending.push(dependency);
+                }
+            });
+    }
+    reachable
+}
+
+/// The binary names of the classes a class refers to, in its constant pool
+/// and in the descriptors of its fields and methods.
+fn referenced_classes(class: &JavaClass) -> BTreeSet<String> {
+    let class_file = class.class_file();
+    let const_pool = &class_file.const_pool;
+    let mut referenced = BTreeSet::new();
+
+    let mut add_type = |descriptor: &str| {
+        if let Some(name) = JvmType::parse(descriptor).ok().as_ref().and_then(JvmType::class_name) {
+            referenced.insert(name.to_owned());
+        }
+    };
+    const_pool.iter().for_each(|constant| {
+        if let ConstantInfo::Class(class_constant) = constant {
+            if let Ok(name) = extract_utf8_constant(const_pool, class_constant.name_index) {
+                match name.utf8_string.starts_with('[') {
+                    true => add_type(&name.utf8_string),
+                    false => add_type(&format!("L{};", name.utf8_string)),
+                }
+            }
+        }
+    });
+    class_file.fields.iter().for_each(|field| {
+        if let Ok(descriptor) = extract_utf8_constant(const_pool, field.descriptor_index) {
+            add_type(&descriptor.utf8_string);
+        }
+    });
+    class_file.methods.iter().for_each(|method| {
+        let Ok(descriptor) = extract_utf8_constant(const_pool, method.descriptor_index) else {
+            return;
+        };
+        if let Ok(method_type) = MethodType::parse(&descriptor.utf8_string) {
+            method_type
+                .params()
+                .iter()
+                .chain(std::iter::once(method_type.ret()))
+                .filter_map(JvmType::class_name)
+                .for_each(|name| {
+                    referenced.insert(name.to_owned());
+                });
+        }
+    });
+    referenced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::hierarchy::ClassIndex;
+    use rstest::rstest;
+
+    fn class_for_test(file: &str) -> JavaClass {
+        JavaClass::parse(&format!("tests/inputs/java/package_cycles/{}", file)).unwrap()
+    }
+
+    #[rstest]
+    #[case::cycle(&["a/A.class", "b/B.class", "c/C.class"], vec!["a", "b"])]
+    #[case::outside_cycle(&["a/A.class", "c/C.class"], vec![])]
+    #[case::single_package(&["b/B.class"], vec![])]
+    fn test_package_cycles(#[case] files: &[&str], #[case] expected: Vec<&str>) {
+        let classes: Vec<JavaClass> = files.iter().map(|file| class_for_test(file)).collect();
+        let index = ClassIndex::new(&classes);
+        let fails = package_cycles(&Project::new(&classes, &index));
+        let packages: Vec<&str> = fails.iter().map(|fail| fail.target.name().as_str()).collect();
+        assert_eq!(packages, expected);
+    }
+}
@@ -1,18 +1,73 @@
-use classfile_parser::{
-    parse_class,
-    types::ClassFile,
-};
+use std::fs;
+
+use classfile_parser::{class_parser, types::ClassFile};
+use nom::error::ErrorKind;
+
 use crate::errors::generic::*;
-use crate::utils::path::*;
 
 /// Parse a file using classfile parser, returning a result containing it.
 ///
 /// * `path`: the path of the classfile
 ///
 pub fn parse_file(path: &String) -> Result<ClassFile, IError> {
-    match parse_class(parse_path_as_absolute(path)?.as_str()) {
-        Ok(class_file) => Ok(class_file),
-        Err(e) => Err(IError::new(GenericErrorKind::ParseError, e)),
+    let bytes = fs::read(path).map_err(|e| {
+        IError::new(GenericErrorKind::InvalidPath, format!("Cannot read {}", path))
+            .with_path(path)
+            .with_source(e)
+    })?;
+    parse_bytes(&bytes).map_err(|e| e.with_path(path))
+}
+
+/// Parse the raw bytes of a class file, e.g. read from an archive.
+///
+/// * `bytes`: the content of the classfile
+pub fn parse_bytes(bytes: &[u8]) -> Result<ClassFile, IError> {
+    match class_parser(bytes) {
+        Ok((_, class_file)) => Ok(class_file),
+        Err(e) => Err(nom_error("class file", bytes, e)),
+    }
+}
+
+/// Decode the body of a method of a class file, for the tests of the
+/// modules working on bytecode.
+///
+/// * `file`: the path of the classfile
+/// * `name`: the name of the method
+/// * `descriptor`: the descriptor of the method, telling overloads apart
+#[cfg(test)]
+pub fn code_for_test(file: &str, name: &str, descriptor: Option<&str>) -> Option<super::code::Code> {
+    use super::class::JavaClass;
+    use super::utils::extract_utf8_constant;
+
+    let class = JavaClass::parse(&String::from(file)).unwrap();
+    let const_pool = &class.class_file().const_pool;
+    let utf8 = |index: u16| extract_utf8_constant(const_pool, index).unwrap().utf8_string.as_str();
+    let method = class
+        .class_file()
+        .methods
+        .iter()
+        .find(|info| {
+            utf8(info.name_index) == name && descriptor.map_or(true, |descriptor| utf8(info.descriptor_index) == descriptor)
+        })
+        .unwrap();
+    super::code::Code::decode(const_pool, method).unwrap()
+}
+
+/// The error of a parser of classfile parser, at the byte it failed at.
+///
+/// * `what`: what was being parsed, e.g. `Code attribute`
+/// * `input`: the bytes given to the parser
+/// * `error`: the error of the parser
+pub fn nom_error(what: &str, input: &[u8], error: nom::Err<(&[u8], ErrorKind)>) -> IError {
+    match error {
+        nom::Err::Incomplete(_) => {
+            IError::new(GenericErrorKind::ParseError, format!("Truncated {}.", what)).with_offset(input.len())
+        }
+        nom::Err::Error((remaining, kind)) | nom::Err::Failure((remaining, kind)) => IError::new(
+            GenericErrorKind::ParseError,
+            format!("Invalid {}: {}.", what, kind.description()),
+        )
+        .with_offset(input.len() - remaining.len()),
     }
 }
 
@@ -52,5 +107,13 @@ mod tests {
         let result = parse_file(&String::from(file));
         assert_eq!(result.is_ok(), expected)
     }
+
+    #[test]
+    fn test_parse_truncated() {
+        let bytes = fs::read("tests/inputs/java/too_many_arguments/TooManyArguments.class").unwrap();
+        let error = parse_bytes(&bytes[..10]).unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Parse);
+        assert!(error.offset().is_some_and(|offset| offset <= 10));
+    }
 }
 
@@ -0,0 +1,102 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::errors::fail::Fail;
+
+use super::class::JavaClass;
+use super::hierarchy::ClassIndex;
+
+/// What a project-level fail is attached to.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Target {
+    /// A class, by its binary name.
+    Class(String),
+    /// A package, by its dotted name, empty for the default package.
+    Package(String),
+}
+
+impl Target {
+    /// The name the fail is reported under.
+    pub fn name(&self) -> &String {
+        match self {
+            Target::Class(name) | Target::Package(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Target::Class(name) => f.write_str(name),
+            Target::Package(name) if name.is_empty() => f.write_str("(default package)"),
+            Target::Package(name) => write!(f, "package {}", name),
+        }
+    }
+}
+
+/// A fail of a project-level rule, along with the class or package it is about.
+#[derive(Debug, Clone)]
+pub struct ProjectFail {
+    pub target: Target,
+    pub fail: Fail,
+}
+
+/// Every analysed class, seen at once by project-level rules.
+#[derive(Debug, Clone, Copy)]
+pub struct Project<'a> {
+    classes: &'a [JavaClass],
+    index: &'a ClassIndex,
+}
+
+impl<'a> Project<'a> {
+    /// * `classes`: the analysed classes
+    /// * `index`: the index of the analysed classes and of the classpath
+    pub fn new(classes: &'a [JavaClass], index: &'a ClassIndex) -> Self {
+        Self { classes, index }
+    }
+
+    pub fn classes(&self) -> &'a [JavaClass] {
+        self.classes
+    }
+
+    pub fn index(&self) -> &'a ClassIndex {
+        self.index
+    }
+
+    /// The analysed class with a binary name.
+    pub fn class(&self, name: &str) -> Option<&'a JavaClass> {
+        self.classes.iter().find(|class| class.name() == name)
+    }
+
+    /// The packages of the analysed classes.
+    pub fn packages(&self) -> BTreeSet<&'a str> {
+        self.classes.iter().map(|class| package_of(class.name())).collect()
+    }
+}
+
+/// The package of a binary class name, empty for the default package.
+pub fn package_of(class_name: &str) -> &str {
+    class_name.rsplit_once('.').map_or("", |(package, _)| package)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::nested("com.example.Foo", "com.example")]
+    #[case::inner("com.example.Foo$Bar", "com.example")]
+    #[case::default_package("Foo", "")]
+    fn test_package_of(#[case] class_name: &str, #[case] expected: &str) {
+        assert_eq!(package_of(class_name), expected);
+    }
+
+    #[rstest]
+    #[case::class(Target::Class(String::from("com.example.Foo")), "com.example.Foo")]
+    #[case::package(Target::Package(String::from("com.example")), "package com.example")]
+    #[case::default_package(Target::Package(String::new()), "(default package)")]
+    fn test_target_display(#[case] target: Target, #[case] expected: &str) {
+        assert_eq!(target.to_string(), expected);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::errors::generic::*;
+
+/// A big-endian cursor over the raw bytes of a class file attribute.
+pub struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn u1(&mut self) -> Result<u8, IError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn u2(&mut self) -> Result<u16, IError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn u4(&mut self) -> Result<u32, IError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Skip bytes, e.g. the content of an attribute no rule reads.
+    pub fn skip(&mut self, length: usize) -> Result<(), IError> {
+        self.take(length).map(|_| ())
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], IError> {
+        match self.bytes.get(self.position..self.position + length) {
+            Some(bytes) => {
+                self.position += length;
+                Ok(bytes)
+            }
+            None => Err(IError::new(
+                GenericErrorKind::InvalidFormat,
+                format!("Unexpected end of attribute at byte {}.", self.position),
+            )
+            .with_offset(self.position)),
+        }
+    }
+}
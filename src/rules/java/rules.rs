@@ -1,26 +1,140 @@
-use classfile_parser::types::ClassFile;
 use lazy_static::lazy_static;
 use regex::Regex;
 
 use crate::{
     errors::{fail::Fail, generic::*},
-    types::rule::*,
+    types::{rule::*, scope::Scope},
 };
 
+use super::class::JavaClass;
+use super::descriptor::MethodType;
+use super::entry_points::{EntryPoints, DEFAULT_ENTRY_POINTS};
 use super::utils::*;
 
-pub fn no_binary_in_names(class_file: ClassFile, file: &str) -> RuleResult {
+/// Reports methods whose name joins two actions with "and" or "or".
+pub struct NoBinaryInNames;
+
+/// Reports methods taking more parameters than the configured maximum.
+pub struct TooManyArguments;
+
+/// Reports methods returning `void`, constructors, `main` and framework entry points aside.
+pub struct CheckNoVoid;
+
+impl Rule for NoBinaryInNames {
+    fn id(&self) -> &'static str {
+        "no_binary_in_names"
+    }
+
+    fn name(&self) -> &'static str {
+        "No \"And\" or \"Or\" in Method Names"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid \"and\"/\"or\" in method names, a hint that a method does more than one thing."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A name joined by and/or usually hides two behaviours that need separate tests."
+    }
+
+    fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+        no_binary_in_names(class, options.scope())
+    }
+}
+
+impl Rule for TooManyArguments {
+    fn id(&self) -> &'static str {
+        "too_many_arguments"
+    }
+
+    fn name(&self) -> &'static str {
+        "Too Many Arguments"
+    }
+
+    fn description(&self) -> &'static str {
+        "Maximum number of parameters a method may take before it is reported."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Every extra parameter multiplies the input combinations a test has to cover."
+    }
+
+    /// A signature fixed by a supertype can't be changed where it is implemented.
+    fn default_scope(&self) -> Scope {
+        Scope::default().without_overrides()
+    }
+
+    fn options(&self) -> Vec<RuleOption> {
+        vec![RuleOption::integer(
+            "max",
+            1,
+            u8::MAX as i64,
+            4,
+            "Maximum number of parameters of a method.",
+        )]
+    }
+
+    fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+        too_many_arguments(class, options.integer("max") as u8, options.scope())
+    }
+}
+
+impl Rule for CheckNoVoid {
+    fn id(&self) -> &'static str {
+        "check_no_void"
+    }
+
+    fn name(&self) -> &'static str {
+        "No Void Return"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid void methods outside constructors, main and framework entry points, as their effects can't be asserted on."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A void method can only be tested by observing its side effects."
+    }
+
+    /// A signature fixed by a supertype can't be changed where it is implemented.
+    fn default_scope(&self) -> Scope {
+        Scope::default().without_overrides()
+    }
+
+    fn options(&self) -> Vec<RuleOption> {
+        vec![RuleOption::strings(
+            "entry_points",
+            DEFAULT_ENTRY_POINTS,
+            "Methods called by frameworks that may return void: name or name(descriptor) globs, or @ and an annotation name.",
+        )]
+    }
+
+    fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+        let entry_points = EntryPoints::new(&options.strings("entry_points"));
+        check_no_void(class, options.scope(), &entry_points)
+    }
+}
+
+fn no_binary_in_names(class: &JavaClass, scope: &Scope) -> Vec<Fail> {
+    let class_file = class.class_file();
     let const_pool = &class_file.const_pool;
 
-    let errors: Vec<Fail> = class_file
+    class_file
         .methods
         .iter()
+        .filter(|method| scope.includes_method(class, method))
         .filter_map(|method| {
             let name = match extract_method_name(const_pool, method.name_index) {
                 Ok(name) => name,
                 Err(e) => return Some(e),
             };
 
+            let descriptor =
+                match extract_method_descriptor(const_pool, method.descriptor_index, name) {
+                    Ok(descriptor) => descriptor,
+                    Err(e) => return Some(e),
+                };
+
             lazy_static! {
                 static ref NO_BINARY_IN_NAMES_REGEX: Regex =
                     Regex::new(r"^(_?|.*_)(and|or|AND|OR)([A-Z]|_).+|.+[a-z](And|Or)[A-Z].*$")
@@ -32,28 +146,22 @@ pub fn no_binary_in_names(class_file: ClassFile, file: &str) -> RuleResult {
                     name.to_owned(),
                     String::from("This method's name contains and/or"),
                     GenericErrorKind::RuleCheckFailed,
-                )),
+                )
+                .with_descriptor(descriptor)),
                 false => None,
             }
         })
-        .collect();
-
-    RuleResult::new(
-        String::from(file),
-        RuleKind::NoBinaryInNames,
-        match errors.is_empty() {
-            true => Ok(()),
-            false => Err(errors),
-        },
-    )
+        .collect()
 }
 
-pub fn check_no_void(class_file: ClassFile, file: &str) -> RuleResult {
+fn check_no_void(class: &JavaClass, scope: &Scope, entry_points: &EntryPoints) -> Vec<Fail> {
+    let class_file = class.class_file();
     let const_pool = &class_file.const_pool;
 
-    let errors: Vec<Fail> = class_file
+    class_file
         .methods
         .iter()
+        .filter(|method| scope.includes_method(class, method))
         .filter_map(|method| {
             let name = match extract_method_name(const_pool, method.name_index) {
                 Ok(name) => name,
@@ -61,47 +169,42 @@ pub fn check_no_void(class_file: ClassFile, file: &str) -> RuleResult {
             };
 
             let descriptor =
-                match extract_method_descriptor(const_pool, method.descriptor_index, &name) {
+                match extract_method_descriptor(const_pool, method.descriptor_index, name) {
                     Ok(descriptor) => descriptor,
                     Err(e) => return Some(e),
                 };
 
-            lazy_static! {
-                static ref MATCH_CONSTRUCTORS_AND_MAIN: Regex =
-                    Regex::new(r".*<init>.*|.*<clinit>.*|main").unwrap();
-            }
-
-            if MATCH_CONSTRUCTORS_AND_MAIN.is_match(name) {
+            if entry_points.includes(const_pool, method, name, descriptor) {
                 return None;
             }
 
-            if &descriptor[descriptor.len() - 1..] == "V" {
+            let method_type = match MethodType::parse(descriptor) {
+                Ok(method_type) => method_type,
+                Err(e) => return Some(Fail::from_error(name.to_owned(), e)),
+            };
+
+            if method_type.ret().is_void() {
                 return Some(Fail::new(
                     name.to_owned(),
                     String::from("This method has return type of void"),
                     GenericErrorKind::RuleCheckFailed,
-                ));
+                )
+                .with_descriptor(descriptor));
             }
 
             None
         })
-        .collect();
-    RuleResult::new(
-        String::from(file),
-        RuleKind::CheckNoVoid,
-        match errors.is_empty() {
-            true => Ok(()),
-            false => Err(errors),
-        },
-    )
+        .collect()
 }
 
-pub fn too_many_arguments(class_file: ClassFile, file: &str, max_arguments: u8) -> RuleResult {
+fn too_many_arguments(class: &JavaClass, max_arguments: u8, scope: &Scope) -> Vec<Fail> {
+    let class_file = class.class_file();
     let const_pool = &class_file.const_pool;
 
-    let errors: Vec<Fail> = class_file
+    class_file
         .methods
         .iter()
+        .filter(|method| scope.includes_method(class, method))
         .filter_map(|method| {
             let name = match extract_method_name(const_pool, method.name_index) {
                 Ok(name) => name,
@@ -109,42 +212,30 @@ pub fn too_many_arguments(class_file: ClassFile, file: &str, max_arguments: u8)
             };
 
             let descriptor =
-                match extract_method_descriptor(const_pool, method.descriptor_index, &name) {
+                match extract_method_descriptor(const_pool, method.descriptor_index, name) {
                     Ok(descriptor) => descriptor,
                     Err(e) => return Some(e),
                 };
-            if count_parameters(descriptor) > max_arguments {
+            let method_type = match MethodType::parse(descriptor) {
+                Ok(method_type) => method_type,
+                Err(e) => return Some(Fail::from_error(name.to_owned(), e)),
+            };
+
+            if method_type.params().len() > max_arguments as usize {
                 return Some(Fail::new(
                     name.to_owned(),
-                    String::from(format!(
-                        "This method has too many arguments (max: {})",
+                    format!(
+                        "This method has too many arguments: {} (max: {})",
+                        method_type.params().len(),
                         max_arguments
-                    )),
+                    ),
                     GenericErrorKind::RuleCheckFailed,
-                ));
+                )
+                .with_descriptor(descriptor));
             }
             None
         })
-        .collect();
-
-    RuleResult::new(
-        String::from(file),
-        RuleKind::TooManyArguments,
-        match errors.is_empty() {
-            true => Ok(()),
-            false => Err(errors),
-        },
-    )
-}
-
-fn count_parameters(descriptor: &String) -> u8 {
-    lazy_static! {
-        static ref MATCH_PARAMETERS: Regex = Regex::new(r"L[^;]*|\(|\).*|").unwrap();
-    }
-    MATCH_PARAMETERS
-        .split(descriptor)
-        .filter(|s| !s.is_empty())
-        .count() as u8
+        .collect()
 }
 
 /* -------------------------------------------------------------------------- */
@@ -153,15 +244,13 @@ fn count_parameters(descriptor: &String) -> u8 {
 
 #[cfg(test)]
 mod tests {
-    use super::super::parse::parse_file;
     use super::*;
     use rstest::rstest;
 
     const INPUTS: &str = "tests/inputs/java";
 
-    fn parse_file_for_test(file: &str) -> (ClassFile, String) {
-        let file = format!("{}/{}", INPUTS, file);
-        (parse_file(&file).unwrap(), String::from(file).to_owned() )
+    fn parse_file_for_test(file: &str) -> JavaClass {
+        JavaClass::parse(&format!("{}/{}", INPUTS, file)).unwrap()
     }
 
     #[rstest]
@@ -174,9 +263,9 @@ mod tests {
     #[case::main_method("check_no_void/valid/MainMethod.class", true)]
     #[case::constructor("check_no_void/valid/ConstructorMethod.class", true)]
     fn test_check_no_void(#[case] file: &str, #[case] expected: bool) {
-        let class_and_file = parse_file_for_test(file);
-        let result = check_no_void(class_and_file.0, class_and_file.1.as_str());
-        assert_eq!(result.result().is_ok(), expected);
+        let class = parse_file_for_test(file);
+        let fails = check_no_void(&class, &Scope::default(), &EntryPoints::default());
+        assert_eq!(fails.is_empty(), expected);
     }
 
     #[rstest]
@@ -189,12 +278,9 @@ mod tests {
     #[case::main_method("check_no_void/valid/MainMethod.class", 0)]
     #[case::constructor("check_no_void/valid/ConstructorMethod.class", 0)]
     fn test_check_no_void_number(#[case] file: &str, #[case] expected: usize) {
-        let class_and_file = parse_file_for_test(file);
-        let result = check_no_void(class_and_file.0, class_and_file.1.as_str());
-        match result.result() {
-            Ok(_) => assert_eq!(0, expected),
-            Err(vector) => assert_eq!(vector.len(), expected)
-        }
+        let class = parse_file_for_test(file);
+        let fails = check_no_void(&class, &Scope::default(), &EntryPoints::default());
+        assert_eq!(fails.len(), expected);
     }
 
     #[rstest]
@@ -215,9 +301,9 @@ mod tests {
     #[case::valid_camel_and("no_binary_in_names/valid/CamelAndNameOnly.class", true)]
     #[case::valid_camel_or("no_binary_in_names/valid/CamelOrNameOnly.class", true)]
     fn test_no_binary_in_names(#[case] file: &str, #[case] expected: bool) {
-        let class_and_file = parse_file_for_test(file);
-        let result = no_binary_in_names(class_and_file.0, class_and_file.1.as_str());
-        assert_eq!(result.result().is_ok(), expected);
+        let class = parse_file_for_test(file);
+        let fails = no_binary_in_names(&class, &Scope::default());
+        assert_eq!(fails.is_empty(), expected);
     }
 
     #[rstest]
@@ -233,9 +319,9 @@ mod tests {
     #[case(9, true)]
     #[case(10, true)]
     fn test_too_many_arguments(#[case] max_arguments: u8, #[case] expected: bool) {
-        let class_and_file = parse_file_for_test("too_many_arguments/TooManyArguments.class");
-        let result = too_many_arguments(class_and_file.0, class_and_file.1.as_str(), max_arguments);
-        assert_eq!(result.result().is_ok(), expected);
+        let class = parse_file_for_test("too_many_arguments/TooManyArguments.class");
+        let fails = too_many_arguments(&class, max_arguments, &Scope::default());
+        assert_eq!(fails.is_empty(), expected);
     }
 
     #[rstest]
@@ -246,12 +332,41 @@ mod tests {
     #[case::four_arguments(4, 1)]
     #[case::five_arguments(5, 0)]
     fn test_too_many_arguments_number(#[case] max_arguments: u8, #[case] expected_errors: usize) {
-        let class_and_file = parse_file_for_test("too_many_arguments/TooManyArgumentsNumber.class");
-        let result = too_many_arguments(class_and_file.0, class_and_file.1.as_str(), max_arguments);
-        match result.result()  {
-            Ok(_) => assert_eq!(0, expected_errors),
-            Err(vector) => assert_eq!(vector.len(), expected_errors),
-        }
+        let class = parse_file_for_test("too_many_arguments/TooManyArgumentsNumber.class");
+        let fails = too_many_arguments(&class, max_arguments, &Scope::default());
+        assert_eq!(fails.len(), expected_errors);
     }
-}
 
+    #[rstest]
+    #[case::two(2, 1)]
+    #[case::three(3, 1)]
+    #[case::four(4, 0)]
+    fn test_too_many_arguments_arrays(#[case] max_arguments: u8, #[case] expected_errors: usize) {
+        let class = parse_file_for_test("descriptor/ArrayArguments.class");
+        let fails = too_many_arguments(&class, max_arguments, &Scope::default());
+        assert_eq!(fails.len(), expected_errors);
+    }
+
+    #[rstest]
+    #[case::default("", 4)]
+    #[case::public("visibility = [\"public\"]", 1)]
+    #[case::instance("static = false", 3)]
+    fn test_check_no_void_scope(#[case] toml: &str, #[case] expected: usize) {
+        let class = parse_file_for_test("scope/Scoped.class");
+        let scope = Scope::default().with_table(&toml.parse().unwrap()).unwrap();
+        let fails = check_no_void(&class, &scope, &EntryPoints::default());
+        assert_eq!(fails.len(), expected);
+    }
+
+    #[rstest]
+    #[case::defaults(&[], 4)]
+    #[case::annotation(&["@Lifecycle"], 3)]
+    #[case::method_name(&["maintain", "domain*"], 2)]
+    fn test_check_no_void_entry_points(#[case] extra: &[&str], #[case] expected: usize) {
+        let class = parse_file_for_test("check_no_void/entry_points/EntryPoints.class");
+        let mut patterns: Vec<String> = DEFAULT_ENTRY_POINTS.iter().map(|pattern| String::from(*pattern)).collect();
+        patterns.extend(extra.iter().map(|pattern| String::from(*pattern)));
+        let fails = check_no_void(&class, &Scope::default(), &EntryPoints::new(&patterns));
+        assert_eq!(fails.len(), expected);
+    }
+}
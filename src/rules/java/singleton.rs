@@ -0,0 +1,238 @@
+use std::fmt;
+
+use classfile_parser::code_attribute::Instruction;
+use classfile_parser::field_info::FieldAccessFlags;
+use classfile_parser::method_info::{MethodAccessFlags, MethodInfo};
+
+use super::class::JavaClass;
+use super::code::{Code, MemberRef, Reference};
+use super::declaration::ClassKind;
+use super::utils::*;
+
+/// How a singleton creates its instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SingletonKind {
+    /// Created by the class initialiser.
+    Eager,
+    /// Created by the accessor on its first call.
+    Lazy,
+    /// Created by the initialiser of a nested holder class, on the first call of the accessor.
+    Holder,
+    /// The only constant of an enum.
+    Enum,
+}
+
+impl fmt::Display for SingletonKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SingletonKind::Eager => f.write_str("eager"),
+            SingletonKind::Lazy => f.write_str("lazily initialised"),
+            SingletonKind::Holder => f.write_str("lazy holder"),
+            SingletonKind::Enum => f.write_str("enum"),
+        }
+    }
+}
+
+/// A class with a single, globally reachable instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Singleton {
+    kind: SingletonKind,
+    /// The binary name of the singleton.
+    class: String,
+    /// The static field holding the instance, in the singleton or its holder.
+    instance: MemberRef,
+    /// The name and descriptor of the static methods returning the instance.
+    accessors: Vec<(String, String)>,
+}
+
+impl Singleton {
+    /// Recognise a singleton from its bytecode: a private constructor, a
+    /// single static field of its own type and a static accessor returning
+    /// it, or an enum with a single constant.
+    ///
+    /// * `class`: the class to recognise
+    pub fn detect(class: &JavaClass) -> Option<Self> {
+        let own_type = format!("L{};", class.name().replace('.', "/"));
+        match class.declaration().kind() {
+            ClassKind::Enum => detect_enum(class, &own_type),
+            ClassKind::Class => detect_class(class, &own_type),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> SingletonKind {
+        self.kind
+    }
+
+    pub fn class(&self) -> &String {
+        &self.class
+    }
+
+    pub fn instance(&self) -> &MemberRef {
+        &self.instance
+    }
+
+    pub fn accessors(&self) -> &Vec<(String, String)> {
+        &self.accessors
+    }
+
+    /// Whether an instruction reaches the instance, calling an accessor or
+    /// reading the field holding it.
+    ///
+    /// * `instruction`: the instruction
+    /// * `reference`: what the instruction refers to
+    pub fn is_reached_by(&self, instruction: &Instruction, reference: &Reference) -> bool {
+        match (instruction, reference) {
+            (Instruction::Invokestatic(_), Reference::Method(method)) => {
+                method.class == self.class
+                    && self
+                        .accessors
+                        .iter()
+                        .any(|(name, descriptor)| *name == method.name && *descriptor == method.descriptor)
+            }
+            (Instruction::Getstatic(_), Reference::Field(field)) => *field == self.instance,
+            _ => false,
+        }
+    }
+}
+
+fn detect_enum(class: &JavaClass, own_type: &str) -> Option<Singleton> {
+    let class_file = class.class_file();
+    let mut constants = class_file
+        .fields
+        .iter()
+        .filter(|field| field.access_flags.contains(FieldAccessFlags::ENUM))
+        .filter_map(|field| extract_utf8_constant(&class_file.const_pool, field.name_index).ok());
+    let constant = constants.next()?;
+    if constants.next().is_some() {
+        return None;
+    }
+
+    Some(Singleton {
+        kind: SingletonKind::Enum,
+        class: class.name().to_owned(),
+        instance: MemberRef {
+            class: class.name().to_owned(),
+            name: constant.utf8_string.clone(),
+            descriptor: own_type.to_owned(),
+        },
+        accessors: Vec::new(),
+    })
+}
+
+fn detect_class(class: &JavaClass, own_type: &str) -> Option<Singleton> {
+    let class_file = class.class_file();
+    let const_pool = &class_file.const_pool;
+    let utf8 = |index: u16| extract_utf8_constant(const_pool, index).ok().map(|constant| &constant.utf8_string);
+    let name = |method: &MethodInfo| utf8(method.name_index);
+    let descriptor = |method: &MethodInfo| utf8(method.descriptor_index);
+
+    let mut constructors = class_file
+        .methods
+        .iter()
+        .filter(|method| name(method).is_some_and(|name| name == "<init>"))
+        .peekable();
+    constructors.peek()?;
+    if !constructors.all(|method| method.access_flags.contains(MethodAccessFlags::PRIVATE)) {
+        return None;
+    }
+
+    let own_fields: Vec<&String> = class_file
+        .fields
+        .iter()
+        .filter(|field| field.access_flags.contains(FieldAccessFlags::STATIC))
+        .filter(|field| utf8(field.descriptor_index).is_some_and(|descriptor| descriptor == own_type))
+        .filter_map(|field| utf8(field.name_index))
+        .collect();
+    // Several instances, e.g. the `ZERO` and `ONE` constants of a value, make no singleton.
+    if own_fields.len() > 1 {
+        return None;
+    }
+    let holds_instance = |field: &MemberRef| {
+        field.descriptor == own_type
+            && match field.class == *class.name() {
+                true => own_fields.contains(&&field.name),
+                false => field.class.starts_with(&format!("{}$", class.name())),
+            }
+    };
+
+    let mut instance: Option<MemberRef> = None;
+    let mut accessors = Vec::new();
+    class_file
+        .methods
+        .iter()
+        .filter(|method| method.access_flags.contains(MethodAccessFlags::STATIC))
+        .filter(|method| !method.access_flags.intersects(MethodAccessFlags::SYNTHETIC | MethodAccessFlags::BRIDGE))
+        .for_each(|method| {
+            let (Some(name), Some(descriptor)) = (name(method), descriptor(method)) else {
+                return;
+            };
+            if !descriptor.ends_with(&format!("){}", own_type)) {
+                return;
+            }
+            let Ok(Some(code)) = Code::decode(const_pool, method) else {
+                return;
+            };
+            let read = code.ops().iter().find_map(|op| match (&op.instruction, &op.reference) {
+                (Instruction::Getstatic(_), Some(Reference::Field(field))) if holds_instance(field) => Some(field),
+                _ => None,
+            });
+            if let Some(field) = read {
+                instance.get_or_insert_with(|| field.clone());
+                accessors.push((name.to_owned(), descriptor.to_owned()));
+            }
+        });
+    let instance = instance?;
+
+    let initialised_eagerly = class_file
+        .methods
+        .iter()
+        .filter(|method| name(method).is_some_and(|name| name == "<clinit>"))
+        .filter_map(|method| Code::decode(const_pool, method).ok().flatten())
+        .any(|code| {
+            code.ops().iter().any(|op| {
+                matches!(
+                    (&op.instruction, &op.reference),
+                    (Instruction::Putstatic(_), Some(Reference::Field(field))) if *field == instance
+                )
+            })
+        });
+    let kind = match (instance.class == *class.name(), initialised_eagerly) {
+        (false, _) => SingletonKind::Holder,
+        (true, true) => SingletonKind::Eager,
+        (true, false) => SingletonKind::Lazy,
+    };
+
+    Some(Singleton { kind, class: class.name().to_owned(), instance, accessors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn singleton_for_test(file: &str) -> Option<Singleton> {
+        Singleton::detect(&JavaClass::parse(&format!("tests/inputs/java/singleton/{}", file)).unwrap())
+    }
+
+    #[rstest]
+    #[case::eager("Eager.class", Some(SingletonKind::Eager))]
+    #[case::lazy("Lazy.class", Some(SingletonKind::Lazy))]
+    #[case::holder("Holder.class", Some(SingletonKind::Holder))]
+    #[case::enum_("Single.class", Some(SingletonKind::Enum))]
+    #[case::enum_of_two("Pair.class", None)]
+    #[case::public_constructor("Shared.class", None)]
+    #[case::several_instances("Amount.class", None)]
+    #[case::holder_class("Holder$Loader.class", None)]
+    #[case::plain("Client.class", None)]
+    fn test_detect(#[case] file: &str, #[case] expected: Option<SingletonKind>) {
+        assert_eq!(singleton_for_test(file).map(|singleton| singleton.kind()), expected);
+    }
+
+    #[test]
+    fn test_holder_instance() {
+        let singleton = singleton_for_test("Holder.class").unwrap();
+        assert_eq!(singleton.instance().class, "Holder$Loader");
+        assert_eq!(singleton.accessors(), &vec![(String::from("get"), String::from("()LHolder;"))]);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    errors::{fail::Fail, generic::*},
+    types::{rule::*, scope::Scope},
+};
+
+use super::code::{MemberRef, Reference};
+use super::common::{check_code, is_in_nest, method_target};
+use super::project::{Project, ProjectFail, Target};
+use super::singleton::{Singleton, SingletonKind};
+
+/// Reports singletons, and the methods of other classes reaching their instance.
+pub struct Singletons;
+
+impl Rule for Singletons {
+    fn id(&self) -> &'static str {
+        "singletons"
+    }
+
+    fn name(&self) -> &'static str {
+        "No Singletons"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid singletons, including lazy holder and enum singletons, and report the methods reaching their instance."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A singleton is global state shared by every test, and its callers cannot be given a test double instead."
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Project
+    }
+
+    fn check_project(&self, project: &Project, options: &RuleOptions) -> Vec<ProjectFail> {
+        singletons(project, options.scope())
+    }
+}
+
+fn singletons(project: &Project, scope: &Scope) -> Vec<ProjectFail> {
+    let detected: Vec<Singleton> = project.classes().iter().filter_map(Singleton::detect).collect();
+
+    let mut fails: Vec<ProjectFail> = detected
+        .iter()
+        .map(|singleton| ProjectFail {
+            target: Target::Class(singleton.class().to_owned()),
+            fail: Fail::new(String::new(), describe_singleton(singleton), GenericErrorKind::RuleCheckFailed),
+        })
+        .collect();
+
+    project.classes().iter().for_each(|class| {
+        let source_class = class.source_class();
+        let callers = check_code(class, scope, |name, descriptor, code| {
+            code.ops()
+                .iter()
+                .filter_map(|op| {
+                    let reference = op.reference.as_ref()?;
+                    let singleton = detected.iter().find(|singleton| {
+                        !is_in_nest(singleton.class(), &source_class) && singleton.is_reached_by(&op.instruction, reference)
+                    })?;
+                    let through = match reference {
+                        Reference::Method(method) => method_target(method),
+                        Reference::Field(field) => format!("{}.{}", field.class, field.name),
+                        _ => return None,
+                    };
+                    Some(
+                        Fail::new(
+                            name.to_owned(),
+                            format!(
+                                "This method reaches the singleton {} through {} at pc {}, global state tests cannot replace",
+                                singleton.class(),
+                                through,
+                                op.pc
+                            ),
+                            GenericErrorKind::RuleCheckFailed,
+                        )
+                        .with_descriptor(descriptor),
+                    )
+                })
+                .collect()
+        });
+        fails.extend(callers.into_iter().map(|fail| ProjectFail { target: Target::Class(class.name().to_owned()), fail }));
+    });
+    fails
+}
+
+/// How a singleton was recognised, e.g. `Foo is an eager singleton: ...`.
+fn describe_singleton(singleton: &Singleton) -> String {
+    let instance = singleton.instance();
+    if singleton.kind() == SingletonKind::Enum {
+        return format!("{} is an enum singleton, {} being its only constant", singleton.class(), instance.name);
+    }
+    let accessors: Vec<String> = singleton
+        .accessors()
+        .iter()
+        .map(|(name, descriptor)| {
+            method_target(&MemberRef {
+                class: singleton.class().to_owned(),
+                name: name.to_owned(),
+                descriptor: descriptor.to_owned(),
+            })
+        })
+        .collect();
+    format!(
+        "{} is a singleton ({}): its constructors are private and {}.{} holds its instance, returned by {}",
+        singleton.class(),
+        singleton.kind(),
+        instance.class,
+        instance.name,
+        accessors.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::class::JavaClass;
+    use crate::rules::java::hierarchy::ClassIndex;
+
+    #[test]
+    fn test_singletons() {
+        let files = ["Eager", "Lazy", "Holder", "Holder$Loader", "Single", "Pair", "Shared", "Amount", "Client"];
+        let classes: Vec<JavaClass> = files
+            .iter()
+            .map(|file| JavaClass::parse(&format!("tests/inputs/java/singleton/{}.class", file)).unwrap())
+            .collect();
+        let index = ClassIndex::new(&classes);
+        let fails = singletons(&Project::new(&classes, &index), &Scope::default());
+        let targets: Vec<(&str, &str)> =
+            fails.iter().map(|fail| (fail.target.name().as_str(), fail.fail.method().as_str())).collect();
+        assert_eq!(
+            targets,
+            vec![
+                ("Eager", ""),
+                ("Lazy", ""),
+                ("Holder", ""),
+                ("Single", ""),
+                ("Client", "total"),
+                ("Client", "total"),
+                ("Client", "lazy"),
+                ("Client", "holder"),
+            ]
+        );
+        assert_eq!(
+            fails[0].fail.message(),
+            "Eager is a singleton (eager): its constructors are private and Eager.INSTANCE holds its instance, returned by Eager.getInstance()"
+        );
+    }
+}
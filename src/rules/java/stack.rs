@@ -0,0 +1,233 @@
+use classfile_parser::code_attribute::Instruction;
+
+use super::code::{Op, Reference};
+use super::descriptor::{JvmType, MethodType};
+
+/// The instruction pushing the value `depth` slots below the top of the
+/// operand stack before the op at `index`, walking back the instructions
+/// before it, `None` when an instruction on the way has an unknown effect on
+/// the stack or the walk leaves the block.
+///
+/// A value keeps its source through `dup` and `checkcast`.
+///
+/// * `block_start`: the offset of the first instruction of the block of the op
+pub fn pushed_by(ops: &[Op], index: usize, mut depth: usize, block_start: usize) -> Option<&Op> {
+    // Before the start of the block, the value may come from any of its predecessors.
+    for op in ops[..index].iter().rev().take_while(|op| op.pc >= block_start) {
+        match &op.instruction {
+            Instruction::Dup if depth < 2 => depth = 0,
+            Instruction::Checkcast(_) if depth == 0 => {}
+            _ => {
+                let (pops, pushes) = stack_effect(op)?;
+                if depth < pushes {
+                    return Some(op);
+                }
+                depth = depth - pushes + pops;
+            }
+        }
+    }
+    None
+}
+
+/// The number of slots an instruction pops from the operand stack and pushes
+/// onto it, `None` for the instructions the walk back does not go through.
+fn stack_effect(op: &Op) -> Option<(usize, usize)> {
+    let field_slots = || match &op.reference {
+        Some(Reference::Field(field)) => JvmType::parse(&field.descriptor).ok().map(|field_type| field_type.slots()),
+        _ => None,
+    };
+    let method_slots = |receiver: usize| match &op.reference {
+        Some(Reference::Method(method)) => MethodType::parse(&method.descriptor).ok().map(|method_type| {
+            let params: usize = method_type.params().iter().map(JvmType::slots).sum();
+            (params + receiver, method_type.ret().slots())
+        }),
+        _ => None,
+    };
+
+    let effect = match &op.instruction {
+        Instruction::Nop
+        | Instruction::Iinc { .. }
+        | Instruction::IincWide { .. }
+        | Instruction::Goto(_)
+        | Instruction::GotoW(_) => (0, 0),
+        Instruction::Aconstnull
+        | Instruction::Iconstm1
+        | Instruction::Iconst0
+        | Instruction::Iconst1
+        | Instruction::Iconst2
+        | Instruction::Iconst3
+        | Instruction::Iconst4
+        | Instruction::Iconst5
+        | Instruction::Fconst0
+        | Instruction::Fconst1
+        | Instruction::Fconst2
+        | Instruction::Bipush(_)
+        | Instruction::Sipush(_)
+        | Instruction::Ldc(_)
+        | Instruction::LdcW(_)
+        | Instruction::New(_)
+        | Instruction::Aload(_)
+        | Instruction::AloadWide(_)
+        | Instruction::Aload0
+        | Instruction::Aload1
+        | Instruction::Aload2
+        | Instruction::Aload3
+        | Instruction::Iload(_)
+        | Instruction::IloadWide(_)
+        | Instruction::Iload0
+        | Instruction::Iload1
+        | Instruction::Iload2
+        | Instruction::Iload3
+        | Instruction::Fload(_)
+        | Instruction::FloadWide(_)
+        | Instruction::Fload0
+        | Instruction::Fload1
+        | Instruction::Fload2
+        | Instruction::Fload3 => (0, 1),
+        Instruction::Lconst0
+        | Instruction::Lconst1
+        | Instruction::Dconst0
+        | Instruction::Dconst1
+        | Instruction::Ldc2W(_)
+        | Instruction::Lload(_)
+        | Instruction::LloadWide(_)
+        | Instruction::Lload0
+        | Instruction::Lload1
+        | Instruction::Lload2
+        | Instruction::Lload3
+        | Instruction::Dload(_)
+        | Instruction::DloadWide(_)
+        | Instruction::Dload0
+        | Instruction::Dload1
+        | Instruction::Dload2
+        | Instruction::Dload3 => (0, 2),
+        Instruction::Pop
+        | Instruction::Astore(_)
+        | Instruction::AstoreWide(_)
+        | Instruction::Astore0
+        | Instruction::Astore1
+        | Instruction::Astore2
+        | Instruction::Astore3
+        | Instruction::Istore(_)
+        | Instruction::IstoreWide(_)
+        | Instruction::Istore0
+        | Instruction::Istore1
+        | Instruction::Istore2
+        | Instruction::Istore3
+        | Instruction::Fstore(_)
+        | Instruction::FstoreWide(_)
+        | Instruction::Fstore0
+        | Instruction::Fstore1
+        | Instruction::Fstore2
+        | Instruction::Fstore3
+        | Instruction::Ifeq(_)
+        | Instruction::Ifne(_)
+        | Instruction::Iflt(_)
+        | Instruction::Ifge(_)
+        | Instruction::Ifgt(_)
+        | Instruction::Ifle(_)
+        | Instruction::Ifnull(_)
+        | Instruction::Ifnonnull(_) => (1, 0),
+        Instruction::Pop2
+        | Instruction::Lstore(_)
+        | Instruction::LstoreWide(_)
+        | Instruction::Lstore0
+        | Instruction::Lstore1
+        | Instruction::Lstore2
+        | Instruction::Lstore3
+        | Instruction::Dstore(_)
+        | Instruction::DstoreWide(_)
+        | Instruction::Dstore0
+        | Instruction::Dstore1
+        | Instruction::Dstore2
+        | Instruction::Dstore3
+        | Instruction::IfIcmpeq(_)
+        | Instruction::IfIcmpne(_)
+        | Instruction::IfIcmplt(_)
+        | Instruction::IfIcmpge(_)
+        | Instruction::IfIcmpgt(_)
+        | Instruction::IfIcmple(_)
+        | Instruction::IfAcmpeq(_)
+        | Instruction::IfAcmpne(_) => (2, 0),
+        Instruction::Iaload
+        | Instruction::Faload
+        | Instruction::Aaload
+        | Instruction::Baload
+        | Instruction::Caload
+        | Instruction::Saload
+        | Instruction::Iadd
+        | Instruction::Isub
+        | Instruction::Imul
+        | Instruction::Idiv
+        | Instruction::Irem
+        | Instruction::Iand
+        | Instruction::Ior
+        | Instruction::Ixor
+        | Instruction::Ishl
+        | Instruction::Ishr
+        | Instruction::Iushr
+        | Instruction::Fadd
+        | Instruction::Fsub
+        | Instruction::Fmul
+        | Instruction::Fdiv
+        | Instruction::Frem
+        | Instruction::Fcmpg
+        | Instruction::Fcmpl
+        | Instruction::L2i
+        | Instruction::L2f
+        | Instruction::D2i
+        | Instruction::D2f => (2, 1),
+        Instruction::Laload
+        | Instruction::Daload
+        | Instruction::Lneg
+        | Instruction::Dneg
+        | Instruction::L2d
+        | Instruction::D2l => (2, 2),
+        Instruction::Iastore
+        | Instruction::Fastore
+        | Instruction::Aastore
+        | Instruction::Bastore
+        | Instruction::Castore
+        | Instruction::Sastore => (3, 0),
+        Instruction::Lshl | Instruction::Lshr | Instruction::Lushr => (3, 2),
+        Instruction::Lastore | Instruction::Dastore => (4, 0),
+        Instruction::Lcmp | Instruction::Dcmpg | Instruction::Dcmpl => (4, 1),
+        Instruction::Ladd
+        | Instruction::Lsub
+        | Instruction::Lmul
+        | Instruction::Ldiv
+        | Instruction::Lrem
+        | Instruction::Land
+        | Instruction::Lor
+        | Instruction::Lxor
+        | Instruction::Dadd
+        | Instruction::Dsub
+        | Instruction::Dmul
+        | Instruction::Ddiv
+        | Instruction::Drem => (4, 2),
+        Instruction::Ineg
+        | Instruction::Fneg
+        | Instruction::I2f
+        | Instruction::F2i
+        | Instruction::I2b
+        | Instruction::I2c
+        | Instruction::I2s
+        | Instruction::Arraylength
+        | Instruction::Newarray(_)
+        | Instruction::Anewarray(_)
+        | Instruction::Checkcast(_)
+        | Instruction::Instanceof(_) => (1, 1),
+        Instruction::I2l | Instruction::I2d | Instruction::F2l | Instruction::F2d => (1, 2),
+        Instruction::Dup => (1, 2),
+        Instruction::Getstatic(_) => (0, field_slots()?),
+        Instruction::Getfield(_) => (1, field_slots()?),
+        Instruction::Putstatic(_) => (field_slots()?, 0),
+        Instruction::Putfield(_) => (1 + field_slots()?, 0),
+        Instruction::Invokestatic(_) => method_slots(0)?,
+        Instruction::Invokevirtual(_) | Instruction::Invokespecial(_) | Instruction::Invokeinterface { .. } => {
+            method_slots(1)?
+        }
+        _ => return None,
+    };
+    Some(effect)
+}
@@ -0,0 +1,133 @@
+use classfile_parser::code_attribute::Instruction;
+
+use crate::{
+    errors::{fail::Fail, generic::*},
+    types::{rule::*, scope::Scope},
+    utils::pattern::GlobSet,
+};
+
+use super::class::JavaClass;
+use super::code::Reference;
+use super::common::{check_code, is_in_nest, method_target};
+
+/// Reports calls to static methods of other classes, pure utilities aside.
+pub struct StaticCalls;
+
+/// Static methods with no effect outside their result, which tests never need to replace.
+pub const DEFAULT_ALLOWED_STATIC_CALLS: &[&str] = &[
+    "java.lang.Math.*",
+    "java.lang.StrictMath.*",
+    "java.lang.*.valueOf",
+    "java.lang.*.parse*",
+    "java.lang.*.toString",
+    "java.lang.*.compare",
+    "java.lang.*.hashCode",
+    "java.lang.String.format",
+    "java.lang.String.join",
+    "java.util.Objects.*",
+    "java.util.Arrays.*",
+    "java.util.Optional.*",
+    "java.util.Collections.empty*",
+    "java.util.Collections.singleton*",
+    "java.util.Collections.unmodifiable*",
+    "java.util.List.of",
+    "java.util.List.copyOf",
+    "java.util.Set.of",
+    "java.util.Set.copyOf",
+    "java.util.Map.of",
+    "java.util.Map.copyOf",
+    "java.util.Map.entry",
+    "java.util.Map.ofEntries",
+    "java.util.stream.**",
+    "kotlin.jvm.internal.Intrinsics.*",
+];
+
+impl Rule for StaticCalls {
+    fn id(&self) -> &'static str {
+        "static_calls"
+    }
+
+    fn name(&self) -> &'static str {
+        "No Static Calls to Collaborators"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid calls to static methods of other classes, outside an allowlist of pure utilities."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A static call is a dependency wired in at compile time, which no test double can stand in for."
+    }
+
+    fn options(&self) -> Vec<RuleOption> {
+        vec![RuleOption::strings(
+            "allowed",
+            DEFAULT_ALLOWED_STATIC_CALLS,
+            "Static methods that may be called: class.method globs, e.g. java.lang.Math.* or java.util.List.of.",
+        )]
+    }
+
+    fn check(&self, class: &JavaClass, options: &RuleOptions) -> Vec<Fail> {
+        static_calls(class, &GlobSet::new(&options.strings("allowed")), options.scope())
+    }
+}
+
+fn static_calls(class: &JavaClass, allowed: &GlobSet, scope: &Scope) -> Vec<Fail> {
+    let source_class = class.source_class();
+
+    check_code(class, scope, |name, descriptor, code| {
+        code.ops()
+            .iter()
+            .filter_map(|op| match (&op.instruction, &op.reference) {
+                (Instruction::Invokestatic(_), Some(Reference::Method(target))) => Some((op.pc, target)),
+                _ => None,
+            })
+            // Static helpers of the class, or of its nest, are implementation details.
+            .filter(|(_, target)| !is_in_nest(&target.class, &source_class))
+            .filter(|(_, target)| !allowed.matches(&format!("{}.{}", target.class, target.name)))
+            .map(|(pc, target)| {
+                Fail::new(
+                    name.to_owned(),
+                    format!(
+                        "This method calls the static method {} at pc {}, a dependency tests cannot replace",
+                        method_target(target),
+                        pc
+                    ),
+                    GenericErrorKind::RuleCheckFailed,
+                )
+                .with_descriptor(descriptor)
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn class_for_test(file: &str) -> JavaClass {
+        JavaClass::parse(&format!("tests/inputs/java/static_calls/{}", file)).unwrap()
+    }
+
+    #[rstest]
+    #[case::defaults(&[], vec!["query", "notifyAll", "notifyAll", "now"])]
+    #[case::allowed_class(&["Mailer.*"], vec!["query", "now"])]
+    #[case::allowed_method(&["java.lang.System.currentTimeMillis"], vec!["query", "notifyAll", "notifyAll"])]
+    fn test_static_calls(#[case] extra: &[&str], #[case] expected: Vec<&str>) {
+        let class = class_for_test("StaticCalls.class");
+        let mut patterns: Vec<String> =
+            DEFAULT_ALLOWED_STATIC_CALLS.iter().map(|pattern| String::from(*pattern)).collect();
+        patterns.extend(extra.iter().map(|pattern| String::from(*pattern)));
+        let fails = static_calls(&class, &GlobSet::new(&patterns), &Scope::default());
+        let methods: Vec<&str> = fails.iter().map(|fail| fail.method().as_str()).collect();
+        assert_eq!(methods, expected);
+    }
+
+    #[test]
+    fn test_static_calls_target() {
+        let class = class_for_test("StaticCalls.class");
+        let fails = static_calls(&class, &GlobSet::new(&[]), &Scope::default());
+        assert!(fails.iter().any(|fail| fail.message().contains("Database.query(java.lang.String) at pc 1")));
+    }
+}
@@ -0,0 +1,141 @@
+use classfile_parser::ClassFile;
+
+use crate::errors::{fail::Fail, generic::IError};
+
+use super::annotations::*;
+use super::utils::*;
+
+/// The annotation recognised in any package without configuration.
+const DEFAULT_ANNOTATION: &str = "SuppressTestability";
+
+/// The rules silenced by annotations on a class and on each of its methods.
+///
+/// An annotation naming no rule in its `value` element silences every rule.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    class: Option<Vec<String>>,
+    methods: Vec<(String, String, Vec<String>)>,
+}
+
+impl Suppressions {
+    /// Collect the suppressions of a class file.
+    ///
+    /// * `class_file`: the class file to read the annotations from
+    /// * `annotation_types`: extra fully qualified annotation names, e.g. `com.example.Generated`
+    pub fn new(class_file: &ClassFile, annotation_types: &[String]) -> Result<Self, IError> {
+        let const_pool = &class_file.const_pool;
+        let descriptors: Vec<String> = annotation_types
+            .iter()
+            .map(|name| format!("L{};", name.replace('.', "/")))
+            .collect();
+
+        let class = suppressed_rules(
+            &extract_annotations(const_pool, &class_file.attributes)?,
+            &descriptors,
+        );
+
+        let mut methods = Vec::new();
+        for method in &class_file.methods {
+            let annotations = extract_annotations(const_pool, &method.attributes)?;
+            if let Some(rules) = suppressed_rules(&annotations, &descriptors) {
+                let name = &extract_utf8_constant(const_pool, method.name_index)?.utf8_string;
+                let descriptor =
+                    &extract_utf8_constant(const_pool, method.descriptor_index)?.utf8_string;
+                methods.push((name.to_owned(), descriptor.to_owned(), rules));
+            }
+        }
+
+        Ok(Self { class, methods })
+    }
+
+    pub fn is_suppressed(&self, rule: &str, fail: &Fail) -> bool {
+        let silences = |rules: &Vec<String>| {
+            rules.is_empty() || rules.iter().any(|key| key == rule)
+        };
+
+        if self.class.as_ref().is_some_and(silences) {
+            return true;
+        }
+
+        self.methods.iter().any(|(name, descriptor, rules)| {
+            name == fail.method() && Some(descriptor) == fail.descriptor() && silences(rules)
+        })
+    }
+}
+
+/// The rules named by the suppressing annotations among `annotations`, if any.
+fn suppressed_rules(annotations: &[Annotation], descriptors: &[String]) -> Option<Vec<String>> {
+    let suppressing: Vec<&Annotation> = annotations
+        .iter()
+        .filter(|annotation| is_suppressing(&annotation.type_descriptor, descriptors))
+        .collect();
+
+    if suppressing.is_empty() {
+        return None;
+    }
+
+    let mut rules = Vec::new();
+    for annotation in suppressing {
+        match annotation.element("value") {
+            Some(value) => rules.extend(value.strings().into_iter().cloned()),
+            None => return Some(Vec::new()),
+        }
+    }
+    Some(rules)
+}
+
+fn is_suppressing(type_descriptor: &str, descriptors: &[String]) -> bool {
+    let simple_name = type_descriptor
+        .trim_start_matches('L')
+        .trim_end_matches(';')
+        .rsplit('/')
+        .next();
+
+    simple_name == Some(DEFAULT_ANNOTATION) || descriptors.iter().any(|descriptor| descriptor == type_descriptor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::class::JavaClass;
+    use super::*;
+    use crate::rules::registry::Registry;
+    use crate::types::rule::ConfiguredRule;
+    use rstest::rstest;
+    use toml::Value;
+
+    const INPUTS: &str = "tests/inputs/java/suppress";
+
+    fn run(file: &str, rule_id: &str, annotation_types: &[String]) -> (usize, usize) {
+        let class = JavaClass::parse(&format!("{}/{}", INPUTS, file)).unwrap();
+        let suppressions = Suppressions::new(class.class_file(), annotation_types).unwrap();
+        let rule = Registry::default().get(rule_id).unwrap();
+        let result = ConfiguredRule::new(rule, &Value::Boolean(true))
+            .unwrap()
+            .run(&class)
+            .suppress(|fail| suppressions.is_suppressed(rule_id, fail));
+        let fails = result.result().as_ref().err().map_or(0, |fails| fails.len());
+        (fails, result.suppressed())
+    }
+
+    #[rstest]
+    #[case::method("SuppressedMethod.class", "check_no_void", 2, 2)]
+    #[case::class("SuppressedClass.class", "check_no_void", 0, 2)]
+    #[case::other_rule("SuppressedClass.class", "no_binary_in_names", 1, 0)]
+    #[case::not_configured("GeneratedClass.class", "check_no_void", 1, 0)]
+    fn test_suppressions(
+        #[case] file: &str,
+        #[case] rule: &str,
+        #[case] fails: usize,
+        #[case] suppressed: usize,
+    ) {
+        assert_eq!(run(file, rule, &[]), (fails, suppressed));
+    }
+
+    #[rstest]
+    #[case::check_no_void("check_no_void", 1)]
+    #[case::no_binary_in_names("no_binary_in_names", 1)]
+    fn test_configured_annotation(#[case] rule: &str, #[case] suppressed: usize) {
+        let annotation_types = vec![String::from("Generated")];
+        assert_eq!(run("GeneratedClass.class", rule, &annotation_types), (0, suppressed));
+    }
+}
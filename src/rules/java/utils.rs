@@ -1,58 +1,65 @@
+use classfile_parser::attribute_info::AttributeInfo;
 use classfile_parser::constant_info::{ConstantInfo, Utf8Constant};
 
 use crate::errors::{fail::Fail, generic::*};
 
+use super::constant_pool::ConstantPool;
+
 /// Extract the utf8 constant from a constant pool with its index
 ///
 /// * `constant_pool`: The constant pool to get the Utf8Constant from
 /// * `index`: The index of the Utf8Constant in the constant pool
 pub fn extract_utf8_constant(
-    constant_pool: &Vec<ConstantInfo>,
+    constant_pool: &[ConstantInfo],
     index: u16,
 ) -> Result<&Utf8Constant, IError> {
-    match constant_pool.get((index - 1) as usize) {
-        Some(constant) => match constant {
-            ConstantInfo::Utf8(constant) => Ok(constant),
-            _ => Err(IError::new(
-                GenericErrorKind::InvalidFormat,
-                String::from("Not an Utf8Constant."),
-            )),
-        },
-        None => {
-            return Err(IError::new(
-                GenericErrorKind::NotFound,
-                String::from("Index out of bound for constant pool."),
-            ))
-        }
-    }
+    Ok(ConstantPool::new(constant_pool).utf8_constant(index)?)
 }
 
 pub fn extract_method_name(
-    constant_pool: &Vec<ConstantInfo>,
+    constant_pool: &[ConstantInfo],
     index: u16,
 ) -> Result<&String, Fail> {
     match extract_utf8_constant(constant_pool, index) {
         Ok(name) => Ok(&name.utf8_string),
-        Err(e) => Err(Fail::new(
-            String::from("N/A"),
-            e.message().clone(),
-            e.kind(),
-        )),
+        Err(e) => Err(Fail::from_error(String::from("N/A"), e)),
     }
 }
 
 pub fn extract_method_descriptor<'a>(
-    constant_pool: &'a Vec<ConstantInfo>,
+    constant_pool: &'a [ConstantInfo],
     index: u16,
     method_name: &'a String,
 ) -> Result<&'a String, Fail> {
     match extract_utf8_constant(constant_pool, index) {
         Ok(descriptor) => Ok(&descriptor.utf8_string),
-        Err(e) => Err(Fail::new(
-            String::from(method_name.to_owned()),
-            e.message().clone(),
-            e.kind(),
-        )),
+        Err(e) => Err(Fail::from_error(method_name.to_owned(), e)),
     }
 }
 
+
+/// Extract the binary name of a class, e.g. `com.example.Foo`, from its class constant
+///
+/// * `constant_pool`: The constant pool to get the ClassConstant from
+/// * `index`: The index of the ClassConstant in the constant pool
+pub fn extract_class_name(constant_pool: &[ConstantInfo], index: u16) -> Result<String, IError> {
+    Ok(ConstantPool::new(constant_pool).class_name(index)?)
+}
+
+/// Find an attribute by its name
+///
+/// * `constant_pool`: The constant pool holding the attribute names
+/// * `attributes`: The attributes of a class, field, method or `Code` attribute
+/// * `name`: The name of the attribute, e.g. `Code`
+pub fn find_attribute<'a>(
+    constant_pool: &[ConstantInfo],
+    attributes: &'a [AttributeInfo],
+    name: &str,
+) -> Result<Option<&'a AttributeInfo>, IError> {
+    for attribute in attributes {
+        if extract_utf8_constant(constant_pool, attribute.attribute_name_index)?.utf8_string == name {
+            return Ok(Some(attribute));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use crate::types::rule::Rule;
+
+use super::java::constructor_work::ConstructorWork;
+use super::java::cyclomatic_complexity::CyclomaticComplexity;
+use super::java::global_state::GlobalState;
+use super::java::new_collaborators::NewCollaborators;
+use super::java::package_cycles::PackageCycles;
+use super::java::rules::*;
+use super::java::singletons::Singletons;
+use super::java::static_calls::StaticCalls;
+
+/// The rules known to the linter, in the order they are configured and reported.
+///
+/// `Registry::default()` holds the built-in rules; more can be registered on top.
+#[derive(Clone)]
+pub struct Registry {
+    rules: Vec<Arc<dyn Rule>>,
+}
+
+impl Registry {
+    /// A registry without any rule.
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a rule, replacing any registered rule with the same id.
+    pub fn register(&mut self, rule: Arc<dyn Rule>) {
+        match self.rules.iter().position(|registered| registered.id() == rule.id()) {
+            Some(index) => self.rules[index] = rule,
+            None => self.rules.push(rule),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<dyn Rule>> {
+        self.rules.iter().find(|rule| rule.id() == id).cloned()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.rules.iter().any(|rule| rule.id() == id)
+    }
+
+    pub fn rules(&self) -> &Vec<Arc<dyn Rule>> {
+        &self.rules
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Arc::new(NoBinaryInNames));
+        registry.register(Arc::new(TooManyArguments));
+        registry.register(Arc::new(CheckNoVoid));
+        registry.register(Arc::new(CyclomaticComplexity));
+        registry.register(Arc::new(PackageCycles));
+        registry.register(Arc::new(StaticCalls));
+        registry.register(Arc::new(NewCollaborators));
+        registry.register(Arc::new(Singletons));
+        registry.register(Arc::new(GlobalState));
+        registry.register(Arc::new(ConstructorWork));
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::fail::Fail;
+    use crate::rules::java::class::JavaClass;
+    use crate::types::rule::RuleOptions;
+
+    struct CustomRule;
+
+    impl Rule for CustomRule {
+        fn id(&self) -> &'static str {
+            "check_no_void"
+        }
+
+        fn name(&self) -> &'static str {
+            "Custom"
+        }
+
+        fn description(&self) -> &'static str {
+            "A rule replacing a built-in one."
+        }
+
+        fn rationale(&self) -> &'static str {
+            "Tests."
+        }
+
+        fn check(&self, _class: &JavaClass, _options: &RuleOptions) -> Vec<Fail> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let ids: Vec<&str> = Registry::default().rules().iter().map(|rule| rule.id()).collect();
+        assert_eq!(ids, vec!["no_binary_in_names", "too_many_arguments", "check_no_void", "cyclomatic_complexity", "package_cycles", "static_calls", "new_collaborators", "singletons", "global_state", "constructor_work"]);
+    }
+
+    #[test]
+    fn test_register_replaces_same_id() {
+        let mut registry = Registry::default();
+        registry.register(Arc::new(CustomRule));
+        assert_eq!(registry.rules().len(), 10);
+        assert_eq!(registry.get("check_no_void").unwrap().name(), "Custom");
+    }
+
+    #[test]
+    fn test_get_unknown_rule() {
+        assert!(Registry::default().get("no_rule").is_none());
+        assert!(!Registry::empty().contains("check_no_void"));
+    }
+}
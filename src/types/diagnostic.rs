@@ -0,0 +1,148 @@
+use std::fmt;
+
+use crate::errors::fail::Fail;
+use crate::errors::generic::{ErrorCategory, IError};
+
+use super::rule::{ConfiguredRule, RuleResult, Severity};
+
+/// The rule id of the diagnostics of files that could not be read.
+pub const UNANALYSED_RULE: &str = "could_not_analyse";
+
+/// A single fail of a rule, flattened with the rule and class it was found in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    rule: &'static str,
+    rule_name: &'static str,
+    severity: Severity,
+    file: String,
+    class: String,
+    source_class: String,
+    location: String,
+    major_version: Option<u16>,
+    method: String,
+    descriptor: Option<String>,
+    signature: String,
+    message: String,
+    /// The stage the analysis failed in, `None` for the fails of the rules.
+    category: Option<ErrorCategory>,
+}
+
+impl Diagnostic {
+    pub fn new(result: &RuleResult, fail: &Fail) -> Self {
+        Self {
+            rule: result.rule(),
+            rule_name: result.name(),
+            severity: result.severity(),
+            file: result.file().to_owned(),
+            class: result.class().to_owned(),
+            source_class: result.source_class().to_owned(),
+            location: result.location().to_owned(),
+            major_version: result.major_version(),
+            method: fail.method().to_owned(),
+            descriptor: fail.descriptor().cloned(),
+            signature: fail.signature(),
+            message: fail.message().to_owned(),
+            category: None,
+        }
+    }
+
+    /// A file that could not be read, or a class a rule panicked on.
+    ///
+    /// * `file`: the file that could not be analysed
+    /// * `class`: the binary name of the class, empty when the file could not be read
+    /// * `rule`: the rule that panicked, `None` when the file could not be read
+    /// * `error`: why the file could not be analysed
+    pub fn unanalysed(file: String, class: String, rule: Option<&ConfiguredRule>, error: &IError) -> Self {
+        let location = match class.is_empty() {
+            true => file.clone(),
+            false => class.clone(),
+        };
+        Self {
+            rule: rule.map_or(UNANALYSED_RULE, ConfiguredRule::id),
+            rule_name: rule.map_or("Could Not Analyse", |rule| rule.rule().name()),
+            severity: Severity::Error,
+            file,
+            source_class: class.clone(),
+            class,
+            location,
+            major_version: None,
+            method: String::new(),
+            descriptor: None,
+            signature: String::new(),
+            message: format!("could not analyse: {}", error.full_message()),
+            category: Some(error.category()),
+        }
+    }
+
+    /// The id of the rule, e.g. `check_no_void`.
+    pub fn rule(&self) -> &'static str {
+        self.rule
+    }
+
+    pub fn rule_name(&self) -> &'static str {
+        self.rule_name
+    }
+
+    /// The stage the analysis failed in, `None` unless the file could not be analysed.
+    pub fn category(&self) -> Option<ErrorCategory> {
+        self.category
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn file(&self) -> &String {
+        &self.file
+    }
+
+    /// The binary name of the class, e.g. `com.example.Foo`.
+    pub fn class(&self) -> &String {
+        &self.class
+    }
+
+    /// The binary name of the top-level class the class is declared in, e.g.
+    /// `com.example.Foo` for `com.example.Foo$1`.
+    pub fn source_class(&self) -> &String {
+        &self.source_class
+    }
+
+    /// The class as it reads in the source code, e.g.
+    /// ``anonymous class in `com.example.Foo.bar()` ``.
+    pub fn location(&self) -> &String {
+        &self.location
+    }
+
+    /// The major version of the class file, e.g. 61 for Java 17, `None` when
+    /// the diagnostic is about a package.
+    pub fn major_version(&self) -> Option<u16> {
+        self.major_version
+    }
+
+    pub fn method(&self) -> &String {
+        &self.method
+    }
+
+    pub fn descriptor(&self) -> Option<&String> {
+        self.descriptor.as_ref()
+    }
+
+    /// The Java signature of the method, e.g. `void run(int)`.
+    pub fn signature(&self) -> &String {
+        &self.signature
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}: ", self.severity, self.location)?;
+        if !self.method.is_empty() {
+            write!(f, "{}: ", self.signature)?;
+        }
+        write!(f, "{} [{}]", self.message, self.rule)
+    }
+}
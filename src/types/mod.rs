@@ -1 +1,3 @@
-pub mod rule;
\ No newline at end of file
+pub mod diagnostic;
+pub mod rule;
+pub mod scope;
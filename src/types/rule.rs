@@ -1,155 +1,548 @@
 use crate::errors::fail::*;
-use crate::rules::java::rules::*;
-use classfile_parser::ClassFile;
+use crate::errors::generic::{GenericErrorKind, IError};
+use crate::rules::java::class::JavaClass;
+use crate::rules::java::kotlin::GeneratedMember;
+use crate::rules::java::project::{Project, ProjectFail, Target};
+use crate::types::scope::{Scope, SCOPE_KEY};
 use colored::Colorize;
 use std::fmt;
-use std::slice::Iter;
+use std::sync::Arc;
 use toml::{Table, Value};
 
+/// The file reported for the results of project-level rules that pass.
+pub const PROJECT_FILE: &str = "(project)";
+
+/// How a fail of a rule affects the outcome of a run.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum RuleKind {
-    NoBinaryInNames,
-    TooManyArguments,
-    CheckNoVoid,
-}
-
-impl RuleKind {
-    pub fn to_key(&self) -> &str {
-        match *self {
-            RuleKind::NoBinaryInNames => "no_binary_in_names",
-            RuleKind::TooManyArguments => "too_many_arguments",
-            RuleKind::CheckNoVoid => "check_no_void",
+pub enum Severity {
+    /// Reported and fails the run.
+    Error,
+    /// Reported without failing the run.
+    Warning,
+}
+
+impl Severity {
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
         }
     }
 
-    pub fn iterator() -> Iter<'static, RuleKind> {
-        static RULE_KIND: [RuleKind; 3] = [
-            RuleKind::NoBinaryInNames,
-            RuleKind::TooManyArguments,
-            RuleKind::CheckNoVoid,
-        ];
-        RULE_KIND.iter()
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "error" => Some(Severity::Error),
+            "warning" => Some(Severity::Warning),
+            _ => None,
+        }
     }
 }
 
-impl fmt::Display for RuleKind {
+impl fmt::Display for Severity {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            RuleKind::NoBinaryInNames => write!(f, "No \"And\" or \"Or\" in Method Names"),
-            RuleKind::TooManyArguments => write!(f, "Too Many Arguments"),
-            RuleKind::CheckNoVoid => write!(f, "No Void Return"),
+        f.write_str(self.to_key())
+    }
+}
+
+/// The kind of value an option accepts in the configuration file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionKind {
+    /// A boolean.
+    Flag,
+    /// An integer within `min..=max`.
+    Integer { min: i64, max: i64 },
+    /// An array of strings.
+    Strings,
+}
+
+/// A typed option of a rule, documented for the configuration schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleOption {
+    name: &'static str,
+    kind: OptionKind,
+    default: Value,
+    description: &'static str,
+}
+
+impl RuleOption {
+    pub fn integer(
+        name: &'static str,
+        min: i64,
+        max: i64,
+        default: i64,
+        description: &'static str,
+    ) -> Self {
+        Self { name, kind: OptionKind::Integer { min, max }, default: Value::Integer(default), description }
+    }
+
+    pub fn flag(name: &'static str, default: bool, description: &'static str) -> Self {
+        Self { name, kind: OptionKind::Flag, default: Value::Boolean(default), description }
+    }
+
+    pub fn strings(name: &'static str, default: &[&str], description: &'static str) -> Self {
+        let default = default.iter().map(|value| Value::String(String::from(*value))).collect();
+        Self { name, kind: OptionKind::Strings, default: Value::Array(default), description }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn kind(&self) -> OptionKind {
+        self.kind
+    }
+
+    pub fn default(&self) -> &Value {
+        &self.default
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Whether a configuration value is acceptable for this option.
+    pub fn accepts(&self, value: &Value) -> bool {
+        match (self.kind, value) {
+            (OptionKind::Flag, Value::Boolean(_)) => true,
+            (OptionKind::Integer { min, max }, Value::Integer(int)) => (min..=max).contains(int),
+            (OptionKind::Strings, Value::Array(values)) => values.iter().all(Value::is_str),
+            _ => false,
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct Rule {
-    kind: RuleKind,
-    parameter: u8,
+/// The option values a rule runs with, its defaults overridden by the configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleOptions {
+    values: Table,
+    scope: Scope,
 }
 
-impl Rule {
-    pub fn new(table: &Table, rule_key: &str) -> Option<Self> {
-        match rule_key {
-            "check_no_void" => Self::select_rule(table.get(rule_key), RuleKind::CheckNoVoid),
-            "no_binary_in_names" => {
-                Self::select_rule(table.get(rule_key), RuleKind::NoBinaryInNames)
-            }
-            "too_many_arguments" => {
-                Self::select_rule(table.get(rule_key), RuleKind::TooManyArguments)
-            }
-            _ => None,
+impl RuleOptions {
+    /// The methods the rule looks at.
+    pub fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    pub fn integer(&self, name: &str) -> i64 {
+        self.get(name).and_then(Value::as_integer).unwrap_or_default()
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.get(name).and_then(Value::as_bool).unwrap_or_default()
+    }
+
+    pub fn strings(&self, name: &str) -> Vec<String> {
+        match self.get(name) {
+            Some(Value::Array(values)) => values
+                .iter()
+                .filter_map(|value| value.as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
         }
     }
+}
 
-    #[cfg(test)]
-    pub fn kind(self) -> RuleKind {
-        self.kind
+/// When a rule runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// On every class on its own, with `Rule::check`.
+    Class,
+    /// Once every class is parsed, on all of them at once, with `Rule::check_project`.
+    Project,
+}
+
+/// A check run on every class, or on the whole project, along with the
+/// metadata the configuration and reports are built from.
+///
+/// Rules are enabled by their id in the configuration file, either with the
+/// shorthand `id = true`, `id = <value of the first option>` when it is an
+/// integer, or with an `[id]` table holding `enabled`, `severity`, `scope` and
+/// the options by name.
+pub trait Rule: Send + Sync {
+    /// The key of the rule in the configuration file, e.g. `check_no_void`.
+    fn id(&self) -> &'static str;
+
+    /// The human-readable name of the rule.
+    fn name(&self) -> &'static str;
+
+    /// What the rule reports.
+    fn description(&self) -> &'static str;
+
+    /// Why what the rule reports hurts testability.
+    fn rationale(&self) -> &'static str;
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
     }
 
-    #[cfg(test)]
-    pub fn parameter(self) -> u8 {
-        self.parameter
+    fn options(&self) -> Vec<RuleOption> {
+        Vec::new()
     }
 
-    fn select_rule(value: Option<&Value>, kind: RuleKind) -> Option<Self> {
-        let Some(value) = value else {
-            return None;
+    /// The methods the rule looks at unless its `scope` table says otherwise.
+    fn default_scope(&self) -> Scope {
+        Scope::default()
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Class
+    }
+
+    /// Check a class, for rules of the `Class` phase.
+    fn check(&self, _class: &JavaClass, _options: &RuleOptions) -> Vec<Fail> {
+        Vec::new()
+    }
+
+    /// Check every class at once, for rules of the `Project` phase.
+    fn check_project(&self, _project: &Project, _options: &RuleOptions) -> Vec<ProjectFail> {
+        Vec::new()
+    }
+}
+
+/// An enabled rule with the options and severity read from the configuration.
+#[derive(Clone)]
+pub struct ConfiguredRule {
+    rule: Arc<dyn Rule>,
+    options: RuleOptions,
+    severity: Severity,
+}
+
+impl ConfiguredRule {
+    /// Configure a rule from its entry in the configuration file, failing
+    /// with the rule id and the key holding an invalid value. Whether the
+    /// entry enables the rule is told by `is_enabled`.
+    pub fn new(rule: Arc<dyn Rule>, value: &Value) -> Result<Self, IError> {
+        let id = rule.id();
+        let invalid = |key: &str, value: &Value| {
+            IError::new(
+                GenericErrorKind::Config,
+                format!("Invalid value for `{}` of rule `{}`: {}", key, id, value),
+            )
         };
+        let schema = rule.options();
+        let mut values: Table = schema
+            .iter()
+            .map(|option| (String::from(option.name()), option.default().clone()))
+            .collect();
+        let mut severity = rule.default_severity();
+        let mut scope = rule.default_scope();
 
         match value {
-            Value::Boolean(flag) => {
-                if *flag {
-                    Some(Self {
-                        kind,
-                        parameter: 0,
-                    })
-                } else {
-                    None
+            Value::Boolean(_) => {}
+            Value::Table(table) => {
+                if let Some(enabled) = table.get("enabled").filter(|enabled| !enabled.is_bool()) {
+                    return Err(invalid("enabled", enabled));
                 }
-            }
-            Value::Integer(int) => {
-                if *int > 0 {
-                    Some(Self {
-                        kind,
-                        parameter: *int as u8,
-                    })
-                } else {
-                    None
+                if let Some(key) = table.get("severity") {
+                    severity = key
+                        .as_str()
+                        .and_then(Severity::from_key)
+                        .ok_or_else(|| invalid("severity", key))?;
+                }
+                if let Some(value) = table.get(SCOPE_KEY) {
+                    scope = value
+                        .as_table()
+                        .and_then(|table| scope.with_table(table))
+                        .ok_or_else(|| invalid(SCOPE_KEY, value))?;
+                }
+                for option in &schema {
+                    if let Some(value) = table.get(option.name()) {
+                        if !option.accepts(value) {
+                            return Err(invalid(option.name(), value));
+                        }
+                        values.insert(String::from(option.name()), value.clone());
+                    }
                 }
             }
-            _ => None,
+            value => {
+                let option = schema
+                    .first()
+                    .filter(|option| matches!(option.kind(), OptionKind::Integer { .. }) && option.accepts(value))
+                    .ok_or_else(|| invalid(id, value))?;
+                values.insert(String::from(option.name()), value.clone());
+            }
+        }
+
+        Ok(Self { rule, options: RuleOptions { values, scope }, severity })
+    }
+
+    /// Whether the entry of a rule in the configuration file enables it:
+    /// anything but `false` or a table with `enabled = false`.
+    pub fn is_enabled(value: &Value) -> bool {
+        match value {
+            Value::Boolean(enabled) => *enabled,
+            Value::Table(table) => table.get("enabled").and_then(Value::as_bool) != Some(false),
+            _ => true,
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.rule.id()
+    }
+
+    #[cfg(test)]
+    pub fn options(&self) -> &RuleOptions {
+        &self.options
+    }
+
+    /// The rule itself, with its metadata.
+    pub fn rule(&self) -> &Arc<dyn Rule> {
+        &self.rule
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn run(&self, class: &JavaClass) -> RuleResult {
+        let fails: Vec<Fail> = self
+            .rule
+            .check(class, &self.options)
+            .into_iter()
+            .map(|fail| fail.with_path(class.file()))
+            .map(|fail| match class.generated_member(fail.method()) {
+                Some(member @ GeneratedMember::PropertyAccessor { .. }) => fail.with_source_name(member.to_string()),
+                _ => fail,
+            })
+            .collect();
+        RuleResult::new(
+            class.file().to_owned(),
+            class.name().to_owned(),
+            self.rule.id(),
+            self.rule.name(),
+            self.severity,
+            match fails.is_empty() {
+                true => Ok(()),
+                false => Err(fails),
+            },
+        )
+        .located(class.source_class(), class.location())
+        .with_major_version(class.declaration().major_version())
+    }
+
+    /// Run a rule of the `Project` phase, with a result for every class or
+    /// package it fails on, or a single passing result.
+    pub fn run_project(&self, project: &Project) -> Vec<RuleResult> {
+        let mut fails: Vec<ProjectFail> = self
+            .rule
+            .check_project(project, &self.options)
+            .into_iter()
+            .map(|ProjectFail { target, fail }| {
+                let fail = match &target {
+                    Target::Class(name) => match project.class(name) {
+                        Some(class) => fail.with_path(class.file()),
+                        None => fail,
+                    },
+                    Target::Package(_) => fail,
+                };
+                ProjectFail { target, fail }
+            })
+            .collect();
+        if fails.is_empty() {
+            return vec![RuleResult::new(
+                String::from(PROJECT_FILE),
+                String::new(),
+                self.rule.id(),
+                self.rule.name(),
+                self.severity,
+                Ok(()),
+            )];
         }
+
+        fails.sort_by(|a, b| a.target.cmp(&b.target));
+        let mut results: Vec<RuleResult> = Vec::new();
+        fails.into_iter().for_each(|ProjectFail { target, fail }| {
+            match results.last_mut() {
+                Some(result) if result.class() == target.name() => result.push(fail),
+                _ => {
+                    let class = match &target {
+                        Target::Class(name) => project.class(name),
+                        Target::Package(_) => None,
+                    };
+                    let result = RuleResult::new(
+                        class.map_or_else(|| target.to_string(), |class| class.file().to_owned()),
+                        target.name().to_owned(),
+                        self.rule.id(),
+                        self.rule.name(),
+                        self.severity,
+                        Err(vec![fail]),
+                    );
+                    results.push(match class {
+                        Some(class) => result
+                            .located(class.source_class(), class.location())
+                            .with_major_version(class.declaration().major_version()),
+                        None => result.located(target.name().to_owned(), target.to_string()),
+                    });
+                }
+            }
+        });
+        results
     }
 
-    pub fn run(&self, class_file: &ClassFile, file: &str) -> RuleResult {
-        match self.kind {
-            RuleKind::CheckNoVoid => check_no_void(class_file.to_owned(), file),
-            RuleKind::NoBinaryInNames => no_binary_in_names(class_file.to_owned(), file),
-            RuleKind::TooManyArguments => too_many_arguments(class_file.to_owned(), file, self.parameter),
+    /// Run the rule on every class of a project, whatever its phase.
+    pub fn run_all(&self, project: &Project) -> Vec<RuleResult> {
+        match self.rule.phase() {
+            Phase::Class => project.classes().iter().map(|class| self.run(class)).collect(),
+            Phase::Project => self.run_project(project),
         }
     }
 }
 
+impl fmt::Debug for ConfiguredRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConfiguredRule")
+            .field("rule", &self.rule.id())
+            .field("options", &self.options)
+            .field("severity", &self.severity)
+            .finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct RuleResult {
     file: String,
-    rule: RuleKind,
+    class: String,
+    /// The binary name of the top-level class the checked class is declared in.
+    source_class: String,
+    /// The checked class as it reads in the source code.
+    location: String,
+    /// The major version of the class file, `None` for packages.
+    major_version: Option<u16>,
+    rule: &'static str,
+    name: &'static str,
+    severity: Severity,
     result: Result<(), Vec<Fail>>,
+    suppressed: usize,
 }
 
 impl RuleResult {
-    pub fn new(file: String, rule: RuleKind, result: Result<(), Vec<Fail>>) -> Self {
-        Self { file, rule, result }
+    pub fn new(
+        file: String,
+        class: String,
+        rule: &'static str,
+        name: &'static str,
+        severity: Severity,
+        result: Result<(), Vec<Fail>>,
+    ) -> Self {
+        let (source_class, location) = (class.clone(), class.clone());
+        Self { file, class, source_class, location, major_version: None, rule, name, severity, result, suppressed: 0 }
+    }
+
+    /// Attribute the result to where the checked class is declared in the source code.
+    ///
+    /// * `source_class`: the binary name of the top-level class
+    /// * `location`: the class as it reads in the source code, e.g. `Foo.Inner`
+    pub fn located(self, source_class: String, location: String) -> Self {
+        Self { source_class, location, ..self }
+    }
+
+    /// Attach the major version of the checked class file.
+    pub fn with_major_version(self, major_version: u16) -> Self {
+        Self { major_version: Some(major_version), ..self }
+    }
+
+    fn push(&mut self, fail: Fail) {
+        match &mut self.result {
+            Ok(()) => self.result = Err(vec![fail]),
+            Err(fails) => fails.push(fail),
+        }
+    }
+
+    /// Drop the fails matching `is_suppressed`, keeping count of them.
+    pub fn suppress<F: FnMut(&Fail) -> bool>(self, mut is_suppressed: F) -> Self {
+        let Err(fails) = self.result else {
+            return self;
+        };
+
+        let (suppressed, fails): (Vec<Fail>, Vec<Fail>) =
+            fails.into_iter().partition(|fail| is_suppressed(fail));
+
+        Self {
+            result: if fails.is_empty() { Ok(()) } else { Err(fails) },
+            suppressed: self.suppressed + suppressed.len(),
+            ..self
+        }
+    }
+
+    /// Split off the errors the rule ran into, keeping only the findings.
+    pub fn split_errors(self) -> (Self, Vec<IError>) {
+        let Err(fails) = self.result else {
+            return (self, Vec::new());
+        };
+
+        let (errors, fails): (Vec<Fail>, Vec<Fail>) = fails.into_iter().partition(|fail| fail.error().is_some());
+        let errors = errors.iter().filter_map(Fail::error).cloned().collect();
+        (Self { result: if fails.is_empty() { Ok(()) } else { Err(fails) }, ..self }, errors)
     }
 
     pub fn file(&self) -> &String {
         &self.file
     }
 
+    /// The binary name of the checked class.
+    pub fn class(&self) -> &String {
+        &self.class
+    }
+
+    pub fn source_class(&self) -> &String {
+        &self.source_class
+    }
+
+    pub fn location(&self) -> &String {
+        &self.location
+    }
+
+    pub fn major_version(&self) -> Option<u16> {
+        self.major_version
+    }
+
     pub fn result(&self) -> &Result<(), Vec<Fail>> {
         &self.result
     }
 
-    pub fn rule(&self) -> RuleKind {
+    /// The id of the rule that produced this result.
+    pub fn rule(&self) -> &'static str {
         self.rule
     }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
+    }
 }
 
 impl fmt::Display for RuleResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let file = format!("{}", format!("(file: {})", self.file()).yellow());
-        let rule = format!("{}", self.rule().to_string().purple());
+        let file = match self.location() == self.class() {
+            true => format!("{}", format!("(file: {})", self.file()).yellow()),
+            false => format!("{}", format!("(file: {}, in: {})", self.file(), self.location()).yellow()),
+        };
+        let rule = format!("{}", format!("{} ({})", self.name(), self.rule()).purple());
         match self.result() {
             Ok(()) => {
                 let ok = format!("{}", "[OK]".green().bold());
                 write!(f, "{} {}, Rule: {}", ok, file, rule)
             }
             Err(fails) => {
-                let ok = format!("{}", "[FAIL]".red().bold());
-                fails.iter().fold(Ok(()), |result, fail| {
-                    result.and_then(|_| writeln!(f, "{} {}, Rule: {}, {}", ok, file, rule, fail))
-                })
+                let ok = match self.severity() {
+                    Severity::Error => format!("{}", "[FAIL]".red().bold()),
+                    Severity::Warning => format!("{}", "[WARN]".yellow().bold()),
+                };
+                fails
+                    .iter()
+                    .try_for_each(|fail| writeln!(f, "{} {}, Rule: {}, {}", ok, file, rule, fail))
             }
         }
     }
@@ -158,35 +551,78 @@ impl fmt::Display for RuleResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rules::registry::Registry;
     use rstest::rstest;
     use toml::Table;
 
-    #[test]
-    fn test_rule_kind_iter() {
-        static RULE_KIND: [RuleKind; 3] = [
-            RuleKind::NoBinaryInNames,
-            RuleKind::TooManyArguments,
-            RuleKind::CheckNoVoid,
-        ];
-        let expected = RULE_KIND.iter();
-        assert_eq!(RuleKind::iterator().eq(expected), true);
-    }
-
     #[rstest]
     #[case::check_no_void("check_no_void = true", "check_no_void", 0)]
     #[case::no_binary_in_names("no_binary_in_names = true", "no_binary_in_names", 0)]
     #[case::too_many_arguments("too_many_arguments = 4", "too_many_arguments", 4)]
-    #[case::no_rule("no_rule = true", "no_rule", 0)]
-    fn test_new_rule(#[case] toml: &str, #[case] key: &str, #[case] parameter: u8) {
+    #[case::too_many_arguments_default("too_many_arguments = true", "too_many_arguments", 4)]
+    #[case::too_many_arguments_table("[too_many_arguments]\nmax = 6", "too_many_arguments", 6)]
+    fn test_new_rule(#[case] toml: &str, #[case] key: &str, #[case] parameter: i64) {
         let table = toml.parse::<Table>().unwrap();
-        let rule = Rule::new(&table, key);
-        match rule {
-            Some(rule) => {
-                assert!(RuleKind::iterator().any(|item| item.eq(&rule.kind())));
-                assert_eq!(rule.parameter(), parameter);
-            },
-            None => assert!(!RuleKind::iterator().any(|item| key == item.to_key())),
-        }
+        let rule = Registry::default().get(key).unwrap();
+        let configured = ConfiguredRule::new(rule, table.get(key).unwrap()).unwrap();
+        assert_eq!(configured.id(), key);
+        assert_eq!(configured.options().integer("max"), parameter);
+        assert_eq!(configured.severity(), Severity::Error);
+    }
+
+    #[rstest]
+    #[case::zero("too_many_arguments = 0", "too_many_arguments")]
+    #[case::negative("too_many_arguments = -1", "too_many_arguments")]
+    #[case::too_large("too_many_arguments = 256", "too_many_arguments")]
+    #[case::too_large_table("[too_many_arguments]\nmax = 256", "max")]
+    #[case::wrong_type("[too_many_arguments]\nmax = \"4\"", "max")]
+    #[case::unknown_severity("[too_many_arguments]\nseverity = \"fatal\"", "severity")]
+    #[case::list_shorthand("too_many_arguments = [4]", "too_many_arguments")]
+    #[case::invalid_scope("[too_many_arguments]\nscope = { visibility = \"public\" }", "scope")]
+    #[case::invalid_enabled("[too_many_arguments]\nenabled = \"no\"", "enabled")]
+    fn test_new_rule_out_of_range(#[case] toml: &str, #[case] key: &str) {
+        let table = toml.parse::<Table>().unwrap();
+        let rule = Registry::default().get("too_many_arguments").unwrap();
+        let error = ConfiguredRule::new(rule, table.get("too_many_arguments").unwrap()).err().unwrap();
+        assert_eq!(error.kind(), GenericErrorKind::Config);
+        assert!(error.message().contains(&format!("`{}` of rule `too_many_arguments`", key)));
+    }
+
+    #[rstest]
+    #[case::enabled("too_many_arguments = 4", true)]
+    #[case::disabled("too_many_arguments = false", false)]
+    #[case::disabled_table("[too_many_arguments]\nenabled = false", false)]
+    #[case::enabled_table("[too_many_arguments]\nmax = 6", true)]
+    fn test_is_enabled(#[case] toml: &str, #[case] expected: bool) {
+        let table = toml.parse::<Table>().unwrap();
+        assert_eq!(ConfiguredRule::is_enabled(table.get("too_many_arguments").unwrap()), expected);
     }
-}
 
+    #[test]
+    fn test_run_error_path() {
+        // Wide.class with the name of its method `big` pointing at constant #0.
+        let file = "tests/inputs/java/constant_pool/Corrupt.class";
+        let class = JavaClass::parse(&String::from(file)).unwrap();
+        let rule = Registry::default().get("check_no_void").unwrap();
+        let result = ConfiguredRule::new(rule, &Value::Boolean(true)).unwrap().run(&class);
+        let fails = result.result().as_ref().unwrap_err();
+        assert!(fails.iter().all(|fail| fail.error().and_then(IError::path).map(String::as_str) == Some(file)));
+    }
+
+    #[test]
+    fn test_new_rule_severity() {
+        let table = "[check_no_void]\nseverity = \"warning\"".parse::<Table>().unwrap();
+        let rule = Registry::default().get("check_no_void").unwrap();
+        let configured = ConfiguredRule::new(rule, table.get("check_no_void").unwrap()).unwrap();
+        assert_eq!(configured.severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn test_new_rule_scope() {
+        let table = "[check_no_void]\nscope = { synthetic = true }".parse::<Table>().unwrap();
+        let rule = Registry::default().get("check_no_void").unwrap();
+        let expected = rule.default_scope().with_table(&"synthetic = true".parse::<Table>().unwrap()).unwrap();
+        let configured = ConfiguredRule::new(rule, table.get("check_no_void").unwrap()).unwrap();
+        assert_eq!(configured.options().scope(), &expected);
+    }
+}
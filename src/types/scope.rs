@@ -0,0 +1,288 @@
+use classfile_parser::method_info::{MethodAccessFlags, MethodInfo};
+use serde_json::{json, Value as Json};
+use toml::{Table, Value};
+
+use crate::rules::java::class::JavaClass;
+use crate::rules::java::utils::extract_utf8_constant;
+
+/// Key of the scope table of a rule in the configuration file.
+pub const SCOPE_KEY: &str = "scope";
+
+/// The access level of a member.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    Public,
+    Protected,
+    /// No access modifier.
+    Package,
+    Private,
+}
+
+impl Visibility {
+    pub const ALL: [Visibility; 4] = [
+        Visibility::Public,
+        Visibility::Protected,
+        Visibility::Package,
+        Visibility::Private,
+    ];
+
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Protected => "protected",
+            Visibility::Package => "package",
+            Visibility::Private => "private",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Visibility::ALL.into_iter().find(|visibility| visibility.to_key() == key)
+    }
+
+    pub fn of(flags: MethodAccessFlags) -> Self {
+        if flags.contains(MethodAccessFlags::PUBLIC) {
+            Visibility::Public
+        } else if flags.contains(MethodAccessFlags::PROTECTED) {
+            Visibility::Protected
+        } else if flags.contains(MethodAccessFlags::PRIVATE) {
+            Visibility::Private
+        } else {
+            Visibility::Package
+        }
+    }
+}
+
+/// The methods a rule looks at, by their access flags, whether a supertype
+/// fixes their signature and whether the language or the Kotlin compiler
+/// declares them.
+///
+/// Set in the table of a rule, e.g.
+/// `scope = { visibility = ["public", "protected"], static = false }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scope {
+    visibilities: Vec<Visibility>,
+    static_members: bool,
+    instance_members: bool,
+    synthetic: bool,
+    bridge: bool,
+    overrides: bool,
+    generated: bool,
+    implicit: bool,
+}
+
+impl Default for Scope {
+    /// Every method written in the source, skipping the compiler-generated ones.
+    fn default() -> Self {
+        Self {
+            visibilities: Visibility::ALL.to_vec(),
+            static_members: true,
+            instance_members: true,
+            synthetic: false,
+            bridge: false,
+            overrides: true,
+            generated: false,
+            implicit: false,
+        }
+    }
+}
+
+impl Scope {
+    /// Override a scope with the keys of a `scope` table, returning `None` on
+    /// an unknown key or an invalid value.
+    pub fn with_table(self, table: &Table) -> Option<Self> {
+        let mut scope = self;
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("visibility", Value::Array(values)) => {
+                    scope.visibilities = values
+                        .iter()
+                        .map(|value| Visibility::from_key(value.as_str()?))
+                        .collect::<Option<Vec<Visibility>>>()?;
+                }
+                ("static", Value::Boolean(flag)) => scope.static_members = *flag,
+                ("instance", Value::Boolean(flag)) => scope.instance_members = *flag,
+                ("synthetic", Value::Boolean(flag)) => scope.synthetic = *flag,
+                ("bridge", Value::Boolean(flag)) => scope.bridge = *flag,
+                ("overrides", Value::Boolean(flag)) => scope.overrides = *flag,
+                ("generated", Value::Boolean(flag)) => scope.generated = *flag,
+                ("implicit", Value::Boolean(flag)) => scope.implicit = *flag,
+                _ => return None,
+            }
+        }
+        Some(scope)
+    }
+
+    /// Skip the methods whose signature is fixed by a known supertype.
+    pub fn without_overrides(self) -> Self {
+        Self { overrides: false, ..self }
+    }
+
+    /// Whether a rule looks at a method of a class.
+    pub fn includes_method(&self, class: &JavaClass, method: &MethodInfo) -> bool {
+        self.includes(method.access_flags)
+            && (self.overrides || class.overridden(method).is_none())
+            && (self.generated || !is_generated(class, method))
+            && (self.implicit || !is_implicit(class, method))
+    }
+
+    /// Whether a rule looks at a method with these access flags.
+    pub fn includes(&self, flags: MethodAccessFlags) -> bool {
+        let is_static = flags.contains(MethodAccessFlags::STATIC);
+        self.visibilities.contains(&Visibility::of(flags))
+            && (if is_static { self.static_members } else { self.instance_members })
+            && (self.synthetic || !flags.contains(MethodAccessFlags::SYNTHETIC))
+            && (self.bridge || !flags.contains(MethodAccessFlags::BRIDGE))
+    }
+
+    /// The scope as it is written in the configuration file.
+    pub fn to_toml(&self) -> Value {
+        let mut table = Table::new();
+        table.insert(
+            String::from("visibility"),
+            Value::Array(
+                self.visibilities
+                    .iter()
+                    .map(|visibility| Value::String(String::from(visibility.to_key())))
+                    .collect(),
+            ),
+        );
+        table.insert(String::from("static"), Value::Boolean(self.static_members));
+        table.insert(String::from("instance"), Value::Boolean(self.instance_members));
+        table.insert(String::from("synthetic"), Value::Boolean(self.synthetic));
+        table.insert(String::from("bridge"), Value::Boolean(self.bridge));
+        table.insert(String::from("overrides"), Value::Boolean(self.overrides));
+        table.insert(String::from("generated"), Value::Boolean(self.generated));
+        table.insert(String::from("implicit"), Value::Boolean(self.implicit));
+        Value::Table(table)
+    }
+
+    /// The JSON Schema of a `scope` table.
+    pub fn schema(&self) -> Json {
+        let visibilities: Vec<&str> = Visibility::ALL.iter().map(|visibility| visibility.to_key()).collect();
+        json!({
+            "type": "object",
+            "description": "The methods the rule looks at, by their access flags.",
+            "properties": {
+                "visibility": {
+                    "type": "array",
+                    "items": { "enum": visibilities },
+                    "description": "The access levels of the methods checked.",
+                },
+                "static": { "type": "boolean", "description": "Whether static methods are checked." },
+                "instance": { "type": "boolean", "description": "Whether instance methods are checked." },
+                "synthetic": { "type": "boolean", "description": "Whether compiler-generated methods, e.g. lambda bodies, are checked." },
+                "bridge": { "type": "boolean", "description": "Whether bridge methods generated for generics are checked." },
+                "overrides": { "type": "boolean", "description": "Whether methods whose signature is fixed by a known supertype are checked." },
+                "generated": { "type": "boolean", "description": "Whether methods the Kotlin compiler generates, e.g. property accessors and `componentN`, are checked." },
+                "implicit": { "type": "boolean", "description": "Whether the members the language declares for enums and records, e.g. `valueOf` and record accessors, are checked." },
+            },
+            "default": serde_json::to_value(self.to_toml()).unwrap_or(Json::Null),
+            "additionalProperties": false,
+        })
+    }
+}
+
+/// Whether a method of a class compiled from Kotlin was generated by the compiler.
+fn is_generated(class: &JavaClass, method: &MethodInfo) -> bool {
+    class.kotlin().is_some()
+        && extract_utf8_constant(&class.class_file().const_pool, method.name_index)
+            .is_ok_and(|name| class.generated_member(&name.utf8_string).is_some())
+}
+
+/// Whether a method is declared by the language for an enum or a record.
+fn is_implicit(class: &JavaClass, method: &MethodInfo) -> bool {
+    let const_pool = &class.class_file().const_pool;
+    match (
+        extract_utf8_constant(const_pool, method.name_index),
+        extract_utf8_constant(const_pool, method.descriptor_index),
+    ) {
+        (Ok(name), Ok(descriptor)) => class.declaration().is_implicit(&name.utf8_string, &descriptor.utf8_string),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::java::hierarchy::ClassIndex;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    #[rstest]
+    #[case::default("", 7)]
+    #[case::public("visibility = [\"public\"]", 4)]
+    #[case::non_private("visibility = [\"public\", \"protected\", \"package\"]", 6)]
+    #[case::instance_only("static = false", 6)]
+    #[case::static_only("instance = false", 1)]
+    #[case::synthetic("synthetic = true", 8)]
+    #[case::compiler_generated("synthetic = true\nbridge = true", 9)]
+    fn test_includes(#[case] toml: &str, #[case] expected: usize) {
+        let class = JavaClass::parse(&String::from("tests/inputs/java/scope/Scoped.class")).unwrap();
+        let scope = Scope::default().with_table(&toml.parse::<Table>().unwrap()).unwrap();
+        let included = class
+            .class_file()
+            .methods
+            .iter()
+            .filter(|method| scope.includes(method.access_flags))
+            .count();
+        assert_eq!(included, expected);
+    }
+
+    #[rstest]
+    #[case::default("", 4)]
+    #[case::generated("generated = true", 14)]
+    fn test_includes_kotlin(#[case] toml: &str, #[case] expected: usize) {
+        let class = JavaClass::parse(&String::from("tests/inputs/java/kotlin/Point.class")).unwrap();
+        let scope = Scope::default().with_table(&toml.parse::<Table>().unwrap()).unwrap();
+        let included = class
+            .class_file()
+            .methods
+            .iter()
+            .filter(|method| scope.includes_method(&class, method))
+            .count();
+        assert_eq!(included, expected);
+    }
+
+    #[rstest]
+    #[case::enum_("Color.class", "", 3)]
+    #[case::enum_implicit("Color.class", "implicit = true", 5)]
+    #[case::record("Point.class", "", 1)]
+    #[case::record_implicit("Point.class", "implicit = true", 10)]
+    fn test_includes_implicit(#[case] file: &str, #[case] toml: &str, #[case] expected: usize) {
+        let class = JavaClass::parse(&format!("tests/inputs/java/modern/{}", file)).unwrap();
+        let scope = Scope::default().with_table(&toml.parse::<Table>().unwrap()).unwrap();
+        let included = class
+            .class_file()
+            .methods
+            .iter()
+            .filter(|method| scope.includes_method(&class, method))
+            .count();
+        assert_eq!(included, expected);
+    }
+
+    #[rstest]
+    #[case::unknown_key("visible = true")]
+    #[case::unknown_visibility("visibility = [\"internal\"]")]
+    #[case::not_a_boolean("static = 1")]
+    fn test_with_table_invalid(#[case] toml: &str) {
+        assert!(Scope::default().with_table(&toml.parse::<Table>().unwrap()).is_none());
+    }
+
+    #[rstest]
+    #[case::overrides(Scope::default(), 6)]
+    #[case::without_overrides(Scope::default().without_overrides(), 4)]
+    fn test_includes_method(#[case] scope: Scope, #[case] expected: usize) {
+        let classes: Vec<JavaClass> = ["Impl", "Base"]
+            .iter()
+            .map(|name| JavaClass::parse(&format!("tests/inputs/java/hierarchy/{}.class", name)).unwrap())
+            .collect();
+        let class = classes[0].clone().with_index(Arc::new(ClassIndex::new(&classes)));
+        let included = class
+            .class_file()
+            .methods
+            .iter()
+            .filter(|method| scope.includes_method(&class, method))
+            .count();
+        assert_eq!(included, expected);
+    }
+}
@@ -1 +1,2 @@
-pub mod path;
\ No newline at end of file
+pub mod path;
+pub mod pattern;
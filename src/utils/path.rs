@@ -1,4 +1,6 @@
-use std::fs::canonicalize;
+use std::fs::{canonicalize, read_dir};
+use std::io;
+use std::path::Path;
 use crate::errors::generic::{
     GenericErrorKind,
     IError,
@@ -8,7 +10,10 @@ use crate::errors::generic::{
 pub fn parse_path_as_absolute(path: &String) -> Result<String, IError> {
     let path = match canonicalize(path) {
         Ok(path) => path,
-        Err(e) => return Err(IError::new(GenericErrorKind::InvalidPath, format!("{:?}", e))),
+        Err(e) => {
+            let error = IError::new(GenericErrorKind::InvalidPath, format!("Cannot find {}", path));
+            return Err(error.with_path(path).with_source(e));
+        }
     };
     match path.with_extension("").to_str() {
         Some(s) => Ok(String::from(s)),
@@ -17,4 +22,42 @@ pub fn parse_path_as_absolute(path: &String) -> Result<String, IError> {
             format!("Failed to get file stem from {:?}", path)
         ))
     }
-}
\ No newline at end of file
+}
+
+/// Recursively collect the `.class` files under a directory, sorted by path.
+///
+/// * `directory`: the directory to walk
+pub fn find_class_files(directory: &Path) -> Result<Vec<String>, IError> {
+    let entries = match read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) => return Err(directory_error(directory, e)),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(e) => return Err(directory_error(directory, e)),
+        };
+        if path.is_dir() {
+            files.append(&mut find_class_files(&path)?);
+        } else if path.extension().is_some_and(|extension| extension == "class") {
+            match path.to_str() {
+                Some(s) => files.push(String::from(s)),
+                None => return Err(IError::new(
+                    GenericErrorKind::Other,
+                    format!("Non UTF-8 path {:?}", path)
+                )),
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn directory_error(directory: &Path, error: io::Error) -> IError {
+    let directory = directory.display().to_string();
+    IError::new(GenericErrorKind::InvalidPath, format!("Cannot read the directory {}", directory))
+        .with_path(&directory)
+        .with_source(error)
+}
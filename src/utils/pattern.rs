@@ -0,0 +1,69 @@
+use regex::Regex;
+
+/// Compile a glob over dotted names into an anchored regex.
+///
+/// `**` matches anything, `*` anything but a `.` and `?` a single character.
+///
+/// * `pattern`: the glob, e.g. `com.example.**.*Dto`
+pub fn glob_to_regex(pattern: &str) -> Regex {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^.]*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(regex::escape(c.to_string().as_str()).as_str()),
+        }
+    }
+    regex.push('$');
+    Regex::new(regex.as_str()).expect("an escaped glob is a valid regex")
+}
+
+/// Globs over dotted names, matching a name when any of them does.
+#[derive(Debug, Clone)]
+pub struct GlobSet {
+    regexes: Vec<Regex>,
+}
+
+impl GlobSet {
+    /// * `patterns`: the globs, see [`glob_to_regex`]
+    pub fn new(patterns: &[String]) -> Self {
+        Self { regexes: patterns.iter().map(|pattern| glob_to_regex(pattern)).collect() }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        self.regexes.iter().any(|regex| regex.is_match(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::exact("com.example.Foo", "com.example.Foo", true)]
+    #[case::star("com.example.*", "com.example.Foo", true)]
+    #[case::star_stops_at_dot("com.example.*", "com.example.sub.Foo", false)]
+    #[case::double_star("com.example.**", "com.example.sub.Foo", true)]
+    #[case::question_mark("Fo?", "Foo", true)]
+    #[case::anchored("Foo", "FooBar", false)]
+    #[case::escaped("Foo$Bar", "Foo$Bar", true)]
+    #[case::signature("set*(Ljava/lang/String;)V", "setName(Ljava/lang/String;)V", true)]
+    fn test_glob_to_regex(#[case] pattern: &str, #[case] name: &str, #[case] expected: bool) {
+        assert_eq!(glob_to_regex(pattern).is_match(name), expected);
+    }
+
+    #[rstest]
+    #[case::any_class("java.lang.Math.max", true)]
+    #[case::method("java.util.List.of", true)]
+    #[case::other_method("java.util.List.add", false)]
+    fn test_glob_set(#[case] name: &str, #[case] expected: bool) {
+        let globs = GlobSet::new(&[String::from("java.lang.Math.*"), String::from("java.util.List.of")]);
+        assert_eq!(globs.matches(name), expected);
+    }
+}
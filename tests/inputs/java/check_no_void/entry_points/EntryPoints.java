@@ -0,0 +1,20 @@
+public class EntryPoints implements Runnable {
+    public static void main(String[] args) {
+    }
+
+    public void main(int arg) {
+    }
+
+    public void maintain() {
+    }
+
+    public void domainEvent() {
+    }
+
+    public void run() {
+    }
+
+    @Lifecycle
+    public void setUp() {
+    }
+}
@@ -0,0 +1,29 @@
+import java.util.List;
+import java.util.function.Supplier;
+
+public abstract class Decoded {
+    private int count;
+
+    public String compute(int value, List<String> names) {
+        StringBuilder builder = new StringBuilder("total");
+        count = Math.max(value, names.size());
+        builder.append(count).append(123456789012L);
+        return builder.toString();
+    }
+
+    public int guarded() {
+        try {
+            return compute(1, List.of()).length();
+        } catch (IllegalStateException e) {
+            return -1;
+        } finally {
+            count = 0;
+        }
+    }
+
+    public Supplier<String> supplier() {
+        return () -> "lambda";
+    }
+
+    protected abstract void hook();
+}
@@ -0,0 +1,13 @@
+public class Wide {
+    public long big() {
+        return 1234567890123L;
+    }
+
+    public double ratio() {
+        return 3.14159;
+    }
+
+    public String name() {
+        return "wide";
+    }
+}
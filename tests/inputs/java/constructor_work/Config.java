@@ -0,0 +1,5 @@
+public class Config {
+    public static String load(String path) {
+        return path;
+    }
+}
@@ -0,0 +1,52 @@
+import java.util.ArrayList;
+import java.util.List;
+
+public class Constructors {
+    private final List<String> names = new ArrayList<>();
+    private final Repository repository;
+    private final String title;
+    private int total;
+
+    public Constructors(Repository repository, String title) {
+        this.repository = repository;
+        this.title = title.trim();
+        repository.register(this);
+    }
+
+    public Constructors(String path) {
+        this.repository = new Repository();
+        this.title = Config.load(path);
+        this.repository.connect();
+        for (int i = 0; i < 3; i++) {
+            total += i;
+        }
+    }
+
+    public Constructors(long count) {
+        this(new Repository(), String.valueOf(count));
+    }
+
+    public Constructors(Repository repository, int total) {
+        this.repository = repository;
+        this.title = defaultTitle();
+        this.total = total;
+    }
+
+    public Constructors(Repository repository, boolean shared) {
+        this.repository = repository;
+        this.title = "joined";
+        (shared ? repository : defaultRepository()).connect();
+    }
+
+    private static Repository defaultRepository() {
+        return null;
+    }
+
+    private static String defaultTitle() {
+        return "untitled";
+    }
+
+    public void refresh() {
+        new Repository().connect();
+    }
+}
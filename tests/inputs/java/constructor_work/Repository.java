@@ -0,0 +1,7 @@
+public class Repository {
+    public void register(Object owner) {
+    }
+
+    public void connect() {
+    }
+}
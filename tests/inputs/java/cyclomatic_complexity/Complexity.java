@@ -0,0 +1,96 @@
+public class Complexity {
+    private int state;
+
+    public int straight(int a) {
+        return a + 1;
+    }
+
+    public int oneIf(int a) {
+        int result = 0;
+        if (a > 0) {
+            result = a;
+        }
+        return result;
+    }
+
+    public int earlyReturn(int a) {
+        if (a > 0) {
+            return 1;
+        }
+        return 2;
+    }
+
+    public int sequential(int a, int b) {
+        int result = 0;
+        if (a > 0) {
+            result += a;
+        }
+        if (b > 0) {
+            result += b;
+        }
+        return result;
+    }
+
+    public boolean conjunction(int a, int b) {
+        return a > 0 && b > 0;
+    }
+
+    public int loop(int a) {
+        int sum = 0;
+        for (int i = 0; i < a; i++) {
+            sum += i;
+        }
+        return sum;
+    }
+
+    public int switchCases(int a) {
+        switch (a) {
+            case 1:
+                return 10;
+            case 2:
+                return 20;
+            case 3:
+                return 30;
+            default:
+                return 0;
+        }
+    }
+
+    public int tryCatch(String a) {
+        try {
+            return Integer.parseInt(a);
+        } catch (NumberFormatException e) {
+            return 0;
+        }
+    }
+
+    public int tryFinally(String a) {
+        try {
+            return Integer.parseInt(a);
+        } finally {
+            state = 0;
+        }
+    }
+
+    public int tangled(int a, int b, int c) {
+        int result = 0;
+        for (int i = 0; i < a; i++) {
+            if (i % 2 == 0 && b > 0) {
+                result += b;
+            } else if (c > 0 || i > b) {
+                result -= c;
+            }
+            while (result > 100) {
+                result /= 2;
+            }
+        }
+        switch (result) {
+            case 0:
+                return -1;
+            case 1:
+                return 1;
+            default:
+                return result;
+        }
+    }
+}
@@ -0,0 +1,9 @@
+public class ArrayArguments {
+    public int four_arrays(int[] arg, long[][] arg2, String[] arg3, double[] arg4) {
+        return arg.length;
+    }
+
+    public int[] two_arrays(int[] arg, int[] arg2) {
+        return arg;
+    }
+}
@@ -0,0 +1,38 @@
+import java.io.Serializable;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.logging.Logger;
+
+public class Globals implements Serializable {
+    private static final long serialVersionUID = 1L;
+    private static final Logger LOGGER = Logger.getLogger("globals");
+    public static final int MAX = 3;
+    public static final String NAME = "globals";
+    public static final List<String> NAMES = List.of("a", "b");
+    @SuppressWarnings("unchecked")
+    public static final List<String> CODES = (List<String>) (Object) List.of("x");
+    public static final List<String> FIRST;
+    public static final List<String> SECOND;
+    private static final Object LOCK = new Object();
+
+    static {
+        FIRST = SECOND = List.of("y");
+    }
+    private static final Map<String, Integer> CACHE = new HashMap<>();
+    private static final int[] TABLE = {1, 2};
+    private static int counter;
+    public static String mode = "dev";
+
+    private final List<String> items = new ArrayList<>();
+
+    public static void increment() {
+        counter++;
+    }
+
+    public void reset() {
+        mode = "prod";
+        Other.value = 1;
+    }
+}
@@ -0,0 +1,6 @@
+public abstract class Base {
+    public abstract void handle(int a, int b, int c, int d, int e);
+
+    protected void hook() {
+    }
+}
@@ -0,0 +1,16 @@
+public class Impl extends Base implements Listener {
+    public void handle(int a, int b, int c, int d, int e) {
+    }
+
+    protected void hook() {
+    }
+
+    public void onEvent() {
+    }
+
+    public void own() {
+    }
+
+    private void hook(int overload) {
+    }
+}
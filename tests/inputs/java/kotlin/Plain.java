@@ -0,0 +1,15 @@
+/** The same members as Point, without the Kotlin metadata. */
+public final class Plain {
+    private int y;
+
+    public final void setY(int y) {
+        this.y = y;
+    }
+
+    public final int component1() {
+        return y;
+    }
+
+    public static void $r8$lambda$move(Plain plain) {
+    }
+}
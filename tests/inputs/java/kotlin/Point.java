@@ -0,0 +1,64 @@
+/** The shape of `data class Point(val x: Int, var y: Int, val isActive: Boolean)` once compiled. */
+@kotlin.Metadata(k = 1, d2 = {"LPoint;", "", "x", "", "y", "isActive", ""})
+public final class Point {
+    private final int x;
+    private int y;
+    private final boolean isActive;
+
+    public Point(int x, int y, boolean isActive) {
+        this.x = x;
+        this.y = y;
+        this.isActive = isActive;
+    }
+
+    public final int getX() {
+        return x;
+    }
+
+    public final int getY() {
+        return y;
+    }
+
+    public final void setY(int y) {
+        this.y = y;
+    }
+
+    public final boolean isActive() {
+        return isActive;
+    }
+
+    public final int getTotal() {
+        return x + y;
+    }
+
+    public final void move() {
+        x();
+    }
+
+    public final int component1() {
+        return x;
+    }
+
+    public final int component2() {
+        return y;
+    }
+
+    public final Point copy(int x, int y, boolean isActive) {
+        return new Point(x, y, isActive);
+    }
+
+    public static Point copy$default(Point point, int x, int y, boolean isActive, int mask, Object marker) {
+        return point.copy(x, y, isActive);
+    }
+
+    public static int access$getX(Point point) {
+        return point.x;
+    }
+
+    public static void $r8$lambda$move(Point point) {
+        point.move();
+    }
+
+    private void x() {
+    }
+}
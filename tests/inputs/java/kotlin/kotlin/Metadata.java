@@ -0,0 +1,12 @@
+package kotlin;
+
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+
+/** Stands for the annotation the Kotlin compiler puts on every class. */
+@Retention(RetentionPolicy.RUNTIME)
+public @interface Metadata {
+    int k() default 1;
+
+    String[] d2() default {};
+}
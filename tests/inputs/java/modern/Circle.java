@@ -0,0 +1,4 @@
+public final class Circle implements Shape {
+    public void draw() {
+    }
+}
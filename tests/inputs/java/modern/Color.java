@@ -0,0 +1,7 @@
+public enum Color {
+    RED,
+    GREEN;
+
+    public void paint() {
+    }
+}
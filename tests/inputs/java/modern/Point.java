@@ -0,0 +1,4 @@
+public record Point(int x, int y, int z, int w, int t) {
+    public void print() {
+    }
+}
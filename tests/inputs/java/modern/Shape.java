@@ -0,0 +1,3 @@
+public sealed interface Shape permits Circle, Square {
+    void draw();
+}
@@ -0,0 +1,4 @@
+public final class Square implements Shape {
+    public void draw() {
+    }
+}
@@ -0,0 +1,48 @@
+import java.util.function.Supplier;
+
+public class Outer {
+    enum Color { RED, GREEN }
+
+    private final Runnable field = new Runnable() {
+        public void run() {
+        }
+    };
+
+    public class Inner {
+        public class Deep {
+            public void deepVoid() {
+            }
+        }
+
+        public void innerVoid() {
+        }
+    }
+
+    public Supplier<Integer> bar(int seed) {
+        return new Supplier<Integer>() {
+            public Integer get() {
+                return seed;
+            }
+
+            public void reset() {
+            }
+        };
+    }
+
+    public Object local() {
+        class Local {
+            public void localVoid() {
+            }
+        }
+        return new Local();
+    }
+
+    public int name(Color color) {
+        switch (color) {
+            case RED:
+                return 1;
+            default:
+                return 2;
+        }
+    }
+}
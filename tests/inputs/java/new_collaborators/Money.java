@@ -0,0 +1,2 @@
+public record Money(int cents) {
+}
@@ -0,0 +1,2 @@
+public class OrderException extends RuntimeException {
+}
@@ -0,0 +1,44 @@
+import java.util.ArrayList;
+import java.util.List;
+
+public class Orders {
+    private final List<String> items = new ArrayList<>();
+
+    public void pay(int amount) {
+        new PaymentGateway().charge(amount);
+    }
+
+    public String describe() {
+        return new StringBuilder("Orders: ").append(items).toString();
+    }
+
+    public void check() {
+        if (items.isEmpty()) {
+            throw new IllegalStateException("No order");
+        }
+        throw new OrderException();
+    }
+
+    public Money total() {
+        return new Money(items.size());
+    }
+
+    public Receipt receipt() {
+        return new Receipt();
+    }
+
+    public List<String> snapshot() {
+        return new ArrayList<>(items);
+    }
+
+    public Runnable task() {
+        return new Runnable() {
+            public void run() {
+            }
+        };
+    }
+
+    public static Orders create() {
+        return new Orders();
+    }
+}
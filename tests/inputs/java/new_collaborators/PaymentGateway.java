@@ -0,0 +1,5 @@
+public class PaymentGateway {
+    public boolean charge(int amount) {
+        return amount > 0;
+    }
+}
@@ -0,0 +1,9 @@
+package a;
+
+import b.B;
+
+public class A {
+    public int size(B b) {
+        return b.count();
+    }
+}
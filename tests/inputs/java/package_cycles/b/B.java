@@ -0,0 +1,11 @@
+package b;
+
+import a.A;
+
+public class B {
+    private A[] parents;
+
+    public int count() {
+        return parents.length;
+    }
+}
@@ -0,0 +1,9 @@
+package c;
+
+import a.A;
+
+public class C {
+    public A create() {
+        return new A();
+    }
+}
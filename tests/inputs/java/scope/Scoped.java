@@ -0,0 +1,23 @@
+import java.util.function.Supplier;
+
+public class Scoped implements Comparable<Scoped> {
+    public void publicInstance() {
+    }
+
+    protected static void protectedStatic() {
+    }
+
+    void packageInstance() {
+    }
+
+    private void privateInstance() {
+    }
+
+    public int compareTo(Scoped other) {
+        return 0;
+    }
+
+    public Supplier<Integer> lambda() {
+        return () -> 1;
+    }
+}
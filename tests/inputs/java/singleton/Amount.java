@@ -0,0 +1,18 @@
+public class Amount {
+    public static final Amount ZERO = new Amount(0);
+    public static final Amount ONE = new Amount(1);
+
+    private final long cents;
+
+    private Amount(long cents) {
+        this.cents = cents;
+    }
+
+    public static Amount zero() {
+        return ZERO;
+    }
+
+    public long cents() {
+        return cents;
+    }
+}
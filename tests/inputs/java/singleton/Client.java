@@ -0,0 +1,21 @@
+public class Client {
+    public int total() {
+        return Eager.getInstance().value() + Single.INSTANCE.value();
+    }
+
+    public Object lazy() {
+        return Lazy.getInstance();
+    }
+
+    public Object holder() {
+        return Holder.get();
+    }
+
+    public Object shared() {
+        return Shared.getDefault();
+    }
+
+    public Object side() {
+        return Pair.LEFT;
+    }
+}
@@ -0,0 +1,14 @@
+public class Eager {
+    private static final Eager INSTANCE = new Eager();
+
+    private Eager() {
+    }
+
+    public static Eager getInstance() {
+        return INSTANCE;
+    }
+
+    public int value() {
+        return 1;
+    }
+}
@@ -0,0 +1,12 @@
+public class Holder {
+    private Holder() {
+    }
+
+    private static class Loader {
+        static final Holder INSTANCE = new Holder();
+    }
+
+    public static Holder get() {
+        return Loader.INSTANCE;
+    }
+}
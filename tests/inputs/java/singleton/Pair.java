@@ -0,0 +1,4 @@
+public enum Pair {
+    LEFT,
+    RIGHT
+}
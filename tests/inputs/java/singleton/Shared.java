@@ -0,0 +1,7 @@
+public class Shared {
+    public static final Shared DEFAULT = new Shared();
+
+    public static Shared getDefault() {
+        return DEFAULT;
+    }
+}
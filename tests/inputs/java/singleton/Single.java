@@ -0,0 +1,7 @@
+public enum Single {
+    INSTANCE;
+
+    public int value() {
+        return 2;
+    }
+}
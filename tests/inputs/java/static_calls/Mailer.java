@@ -0,0 +1,4 @@
+public class Mailer {
+    public static void send(String to) {
+    }
+}
@@ -0,0 +1,44 @@
+import java.util.Collections;
+import java.util.List;
+import java.util.Objects;
+
+public class StaticCalls {
+    public int allowed(int a, int b) {
+        return Math.max(a, b) + Objects.hash(a, b);
+    }
+
+    public List<String> factories() {
+        return Collections.emptyList();
+    }
+
+    public String query(String sql) {
+        return Database.query(sql);
+    }
+
+    public void notifyAll(String first, String second) {
+        Mailer.send(first);
+        Mailer.send(second);
+    }
+
+    public long now() {
+        return System.currentTimeMillis();
+    }
+
+    public int helper() {
+        return twice(2);
+    }
+
+    private static int twice(int x) {
+        return x * 2;
+    }
+
+    public int nested() {
+        return Inner.three();
+    }
+
+    static class Inner {
+        static int three() {
+            return 3;
+        }
+    }
+}
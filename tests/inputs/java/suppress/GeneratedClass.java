@@ -0,0 +1,8 @@
+@Generated
+public class GeneratedClass {
+    public void first() {}
+
+    public int readAndWrite() {
+        return 0;
+    }
+}
@@ -0,0 +1,3 @@
+public @interface SuppressTestability {
+    String[] value();
+}
@@ -0,0 +1,10 @@
+@SuppressTestability("check_no_void")
+public class SuppressedClass {
+    public void first() {}
+
+    public void second() {}
+
+    public int readAndWrite() {
+        return 0;
+    }
+}
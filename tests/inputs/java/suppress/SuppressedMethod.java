@@ -0,0 +1,11 @@
+public class SuppressedMethod {
+    @SuppressTestability("check_no_void")
+    public void suppressed() {}
+
+    public void reported() {}
+
+    public void overloaded(int value) {}
+
+    @SuppressTestability({"no_binary_in_names", "check_no_void"})
+    public void overloaded(String value) {}
+}